@@ -0,0 +1,611 @@
+use crate::disks::DiskInfo;
+use crate::history::{RingBuffer, SystemSample};
+use crate::process::ProcessInfo;
+use crate::query::{ParseError, Query, QueryContext};
+use crate::system::KillSignal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::Pid;
+
+/// What an alert rule watches, with the threshold it is compared against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum AlertCondition {
+    /// Any process matching a filter query, in percent of all cores
+    ProcessCpuAbove { filter: String, percent: f64 },
+    /// Any process matching a filter query, resident memory in MB
+    ProcessMemoryAbove { filter: String, megabytes: f64 },
+    /// Whole-system CPU usage in percent
+    SystemCpuAbove { percent: f64 },
+    /// Used memory in percent of total memory
+    SystemMemoryAbove { percent: f64 },
+    /// Free space in GB on one mount point, or on any disk when empty
+    DiskFreeBelow { mount_point: String, gigabytes: f64 },
+}
+
+impl fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let processes = |filter: &str| {
+            if filter.is_empty() {
+                String::from("any process")
+            } else {
+                format!("processes matching '{}'", filter)
+            }
+        };
+        match self {
+            AlertCondition::ProcessCpuAbove { filter, percent } => {
+                write!(f, "CPU of {} above {}%", processes(filter), percent)
+            }
+            AlertCondition::ProcessMemoryAbove { filter, megabytes } => {
+                write!(f, "Memory of {} above {} MB", processes(filter), megabytes)
+            }
+            AlertCondition::SystemCpuAbove { percent } => {
+                write!(f, "System CPU above {}%", percent)
+            }
+            AlertCondition::SystemMemoryAbove { percent } => {
+                write!(f, "System memory above {}%", percent)
+            }
+            AlertCondition::DiskFreeBelow {
+                mount_point,
+                gigabytes,
+            } if mount_point.is_empty() => write!(f, "Any disk below {} GB free", gigabytes),
+            AlertCondition::DiskFreeBelow {
+                mount_point,
+                gigabytes,
+            } => write!(f, "{} below {} GB free", mount_point, gigabytes),
+        }
+    }
+}
+
+impl AlertCondition {
+    fn threshold(&self) -> f64 {
+        match self {
+            AlertCondition::ProcessCpuAbove { percent, .. }
+            | AlertCondition::SystemCpuAbove { percent }
+            | AlertCondition::SystemMemoryAbove { percent } => *percent,
+            AlertCondition::ProcessMemoryAbove { megabytes, .. } => *megabytes,
+            AlertCondition::DiskFreeBelow { gigabytes, .. } => *gigabytes,
+        }
+    }
+
+    /// Whether the condition is breached by values below the threshold
+    fn is_lower_bound(&self) -> bool {
+        matches!(self, AlertCondition::DiskFreeBelow { .. })
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            AlertCondition::ProcessMemoryAbove { .. } => " MB",
+            AlertCondition::DiskFreeBelow { .. } => " GB free",
+            _ => "%",
+        }
+    }
+
+    /// The filter query of a process condition, parsed; None for other conditions
+    fn parse_filter(&self) -> Result<Option<Query>, ParseError> {
+        match self {
+            AlertCondition::ProcessCpuAbove { filter, .. }
+            | AlertCondition::ProcessMemoryAbove { filter, .. } => Query::parse(filter).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Current values and what they were measured on: every process matching
+    /// `filter` for process conditions, otherwise at most one value.
+    /// `filter` is the parsed filter of a process condition.
+    fn measure(&self, filter: Option<&Query>, inputs: &AlertInputs) -> Vec<Measurement> {
+        match self {
+            AlertCondition::ProcessCpuAbove { .. } => {
+                let cores = inputs.context.cpu_cores.max(1) as f64;
+                matching(filter, inputs, |p| f64::from(p.cpu_usage) / cores)
+            }
+            AlertCondition::ProcessMemoryAbove { .. } => {
+                matching(filter, inputs, |p| p.memory_usage as f64 / 1024.0 / 1024.0)
+            }
+            AlertCondition::SystemCpuAbove { .. } => inputs
+                .sample
+                .map(|sample| Measurement {
+                    value: f64::from(sample.cpu_usage),
+                    subject: String::from("System CPU"),
+                    process: None,
+                })
+                .into_iter()
+                .collect(),
+            AlertCondition::SystemMemoryAbove { .. } => inputs
+                .sample
+                .map(|sample| Measurement {
+                    value: sample.used_memory as f64 / inputs.context.total_memory.max(1) as f64
+                        * 100.0,
+                    subject: String::from("System memory"),
+                    process: None,
+                })
+                .into_iter()
+                .collect(),
+            AlertCondition::DiskFreeBelow { mount_point, .. } => inputs
+                .disks
+                .iter()
+                .filter(|d| mount_point.is_empty() || d.mount_point.as_os_str() == &**mount_point)
+                .map(|d| Measurement {
+                    value: d.available_space as f64 / 1024.0 / 1024.0 / 1024.0,
+                    subject: format!("Disk {}", d.mount_point.display()),
+                    process: None,
+                })
+                .min_by(|a, b| a.value.total_cmp(&b.value))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// `metric` of every process matching `filter`
+fn matching(
+    filter: Option<&Query>,
+    inputs: &AlertInputs,
+    metric: impl Fn(&ProcessInfo) -> f64,
+) -> Vec<Measurement> {
+    inputs
+        .processes
+        .iter()
+        .filter(|p| filter.is_none_or(|query| query.matches(p, inputs.context)))
+        .map(|p| Measurement {
+            value: metric(p),
+            subject: format!("{} (PID {})", p.name.to_string_lossy(), p.pid),
+            process: Some((p.pid, p.start_time)),
+        })
+        .collect()
+}
+
+struct Measurement {
+    value: f64,
+    subject: String,
+    /// PID and start time of the process the value was measured on, for process rules
+    process: Option<(Pid, u64)>,
+}
+
+/// Something a rule does when it fires, besides notifying
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum AlertAction {
+    /// Send a signal to the process that breached the rule
+    Signal { signal: KillSignal },
+    /// Change the nice value of the process that breached the rule
+    Renice { nice: i32 },
+    /// Run a shell command with the alert details in `TARNER_*` environment variables
+    Command { command: String },
+    /// Export every process to a CSV file, by default in the snapshot folder
+    Snapshot {
+        #[serde(default)]
+        directory: Option<PathBuf>,
+    },
+}
+
+impl fmt::Display for AlertAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertAction::Signal { signal } => write!(f, "send {}", signal),
+            AlertAction::Renice { nice } => write!(f, "renice to {}", nice),
+            AlertAction::Command { command } => write!(f, "run `{}`", command),
+            AlertAction::Snapshot { .. } => write!(f, "export a process snapshot"),
+        }
+    }
+}
+
+/// A named condition that must hold for `duration_secs` before it fires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub condition: AlertCondition,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long the threshold must be breached before the alert fires
+    #[serde(default)]
+    pub duration_secs: u64,
+    /// How far back past the threshold the value must go before the alert resolves
+    #[serde(default)]
+    pub hysteresis: f64,
+    /// Minimum time between two firings of this rule
+    #[serde(default)]
+    pub cooldown_secs: u64,
+    /// Run when the rule fires, in order
+    #[serde(default)]
+    pub actions: Vec<AlertAction>,
+    /// Log what the actions would do instead of doing it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AlertRule {
+    /// The measurement furthest past the threshold
+    fn worst<'a>(
+        &self,
+        measurements: impl IntoIterator<Item = &'a Measurement>,
+    ) -> Option<&'a Measurement> {
+        let measurements = measurements.into_iter();
+        if self.condition.is_lower_bound() {
+            measurements.min_by(|a, b| a.value.total_cmp(&b.value))
+        } else {
+            measurements.max_by(|a, b| a.value.total_cmp(&b.value))
+        }
+    }
+
+    fn breached(&self, value: f64) -> bool {
+        let threshold = self.condition.threshold();
+        if self.condition.is_lower_bound() {
+            value < threshold
+        } else {
+            value > threshold
+        }
+    }
+
+    fn recovered(&self, value: f64) -> bool {
+        let threshold = self.condition.threshold();
+        if self.condition.is_lower_bound() {
+            value >= threshold + self.hysteresis
+        } else {
+            value <= threshold - self.hysteresis
+        }
+    }
+}
+
+/// Rules offered until the user writes their own in the config file
+pub fn default_rules() -> Vec<AlertRule> {
+    vec![
+        AlertRule {
+            name: String::from("Runaway process"),
+            condition: AlertCondition::ProcessCpuAbove {
+                filter: String::new(),
+                percent: 90.0,
+            },
+            enabled: true,
+            duration_secs: 30,
+            hysteresis: 10.0,
+            cooldown_secs: 300,
+            actions: Vec::new(),
+            dry_run: false,
+        },
+        AlertRule {
+            name: String::from("Memory nearly full"),
+            condition: AlertCondition::SystemMemoryAbove { percent: 95.0 },
+            enabled: true,
+            duration_secs: 10,
+            hysteresis: 5.0,
+            cooldown_secs: 300,
+            actions: Vec::new(),
+            dry_run: false,
+        },
+        AlertRule {
+            name: String::from("Disk nearly full"),
+            condition: AlertCondition::DiskFreeBelow {
+                mount_point: String::new(),
+                gigabytes: 5.0,
+            },
+            enabled: true,
+            duration_secs: 0,
+            hysteresis: 1.0,
+            cooldown_secs: 600,
+            actions: Vec::new(),
+            dry_run: false,
+        },
+    ]
+}
+
+/// Everything a rule can be measured against on one refresh
+pub struct AlertInputs<'a> {
+    pub processes: &'a [ProcessInfo],
+    pub sample: Option<&'a SystemSample>,
+    pub disks: &'a [DiskInfo],
+    pub context: QueryContext,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    Fired,
+    Resolved,
+    /// An action run (or dry-run) because a rule fired
+    Action,
+}
+
+impl fmt::Display for AlertKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertKind::Fired => write!(f, "FIRING"),
+            AlertKind::Resolved => write!(f, "RESOLVED"),
+            AlertKind::Action => write!(f, "ACTION"),
+        }
+    }
+}
+
+/// A rule firing or resolving, or an action it triggered
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub rule: String,
+    /// Position of the rule in [`AlertEngine::rules`]
+    pub rule_index: usize,
+    pub kind: AlertKind,
+    pub message: String,
+    /// Process the rule was measured on, for process rules
+    pub pid: Option<Pid>,
+    /// Measured value, in the unit of the rule's condition
+    pub value: Option<f64>,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Where a rule is in its fire/resolve cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleStatus {
+    Ok,
+    /// Breached, waiting out the rule's duration or cooldown
+    Pending,
+    Firing,
+    Disabled,
+    /// The rule's filter query does not parse
+    Invalid,
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleState {
+    /// When each breaching process (or the system value, keyed None) crossed the threshold
+    breached_since: HashMap<Option<(Pid, u64)>, Instant>,
+    firing: bool,
+    last_fired: Option<Instant>,
+    last_value: Option<f64>,
+    error: Option<String>,
+}
+
+/// Evaluates alert rules on each refresh and keeps a history of what fired
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    /// Filter queries of the rules, parsed once when the rules are loaded
+    filters: Vec<Result<Option<Query>, ParseError>>,
+    states: Vec<RuleState>,
+    history: RingBuffer<AlertEvent>,
+}
+
+impl AlertEngine {
+    pub const HISTORY_CAPACITY: usize = 500;
+
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        AlertEngine {
+            filters: rules.iter().map(|r| r.condition.parse_filter()).collect(),
+            states: vec![RuleState::default(); rules.len()],
+            rules,
+            history: RingBuffer::new(Self::HISTORY_CAPACITY),
+        }
+    }
+
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Enable or disable a rule; disabling clears its state without a resolve event
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        if let (Some(rule), Some(state)) = (self.rules.get_mut(index), self.states.get_mut(index)) {
+            rule.enabled = enabled;
+            *state = RuleState::default();
+        }
+    }
+
+    pub fn status(&self, index: usize) -> RuleStatus {
+        match (self.rules.get(index), self.states.get(index)) {
+            (Some(rule), _) if !rule.enabled => RuleStatus::Disabled,
+            (_, Some(state)) if state.error.is_some() => RuleStatus::Invalid,
+            (_, Some(state)) if state.firing => RuleStatus::Firing,
+            (_, Some(state)) if !state.breached_since.is_empty() => RuleStatus::Pending,
+            _ => RuleStatus::Ok,
+        }
+    }
+
+    /// Last measured value of a rule, if anything matched
+    pub fn last_value(&self, index: usize) -> Option<f64> {
+        self.states.get(index).and_then(|s| s.last_value)
+    }
+
+    /// Why a rule cannot be evaluated, if it cannot
+    pub fn error(&self, index: usize) -> Option<&str> {
+        self.states.get(index).and_then(|s| s.error.as_deref())
+    }
+
+    /// Fired and resolved alerts, oldest first
+    pub fn history(&self) -> &RingBuffer<AlertEvent> {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history = RingBuffer::new(Self::HISTORY_CAPACITY);
+    }
+
+    /// Check every enabled rule at `now`, returning the alerts that fired or resolved
+    pub fn evaluate(&mut self, inputs: &AlertInputs, now: Instant) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        let rules = self.rules.iter().zip(&self.filters);
+        for (index, ((rule, filter), state)) in rules.zip(self.states.iter_mut()).enumerate() {
+            if !rule.enabled {
+                continue;
+            }
+            let filter = match filter {
+                Ok(filter) => {
+                    state.error = None;
+                    filter.as_ref()
+                }
+                Err(error) => {
+                    state.error = Some(error.to_string());
+                    continue;
+                }
+            };
+            let measurements = rule.condition.measure(filter, inputs);
+            let measurement = rule.worst(&measurements);
+            state.last_value = measurement.map(|m| m.value);
+
+            let unit = rule.condition.unit();
+            if state.firing {
+                // With nothing left to measure (e.g. the process exited) the alert resolves
+                let recovered = measurement.is_none_or(|m| rule.recovered(m.value));
+                if recovered {
+                    state.firing = false;
+                    state.breached_since.clear();
+                    let message = match measurement {
+                        Some(m) => format!(
+                            "{}: {} back to {:.1}{}",
+                            rule.name, m.subject, m.value, unit
+                        ),
+                        None => format!("{}: nothing matches any more", rule.name),
+                    };
+                    events.push(event(
+                        index,
+                        rule,
+                        AlertKind::Resolved,
+                        message,
+                        measurement,
+                    ));
+                }
+                continue;
+            }
+
+            // Each process must hold the breach on its own, so actions only ever
+            // reach a process that stayed past the threshold for the whole duration
+            let breaching: Vec<&Measurement> = measurements
+                .iter()
+                .filter(|m| rule.breached(m.value))
+                .collect();
+            state
+                .breached_since
+                .retain(|key, _| breaching.iter().any(|m| m.process == *key));
+            let hold = Duration::from_secs(rule.duration_secs);
+            let held: Vec<&Measurement> = breaching
+                .into_iter()
+                .filter(|m| {
+                    let since = *state.breached_since.entry(m.process).or_insert(now);
+                    now.duration_since(since) >= hold
+                })
+                .collect();
+            let Some(measurement) = rule.worst(held) else {
+                continue;
+            };
+            let cooled = state.last_fired.is_none_or(|last| {
+                now.duration_since(last) >= Duration::from_secs(rule.cooldown_secs)
+            });
+            if cooled {
+                state.firing = true;
+                state.last_fired = Some(now);
+                let message = format!(
+                    "{}: {} at {:.1}{} (threshold {:.1}{})",
+                    rule.name,
+                    measurement.subject,
+                    measurement.value,
+                    unit,
+                    rule.condition.threshold(),
+                    unit
+                );
+                events.push(event(
+                    index,
+                    rule,
+                    AlertKind::Fired,
+                    message,
+                    Some(measurement),
+                ));
+            }
+        }
+
+        for event in &events {
+            self.history.push(event.clone());
+        }
+        events
+    }
+
+    /// Add an action taken for the `fired` alert to the history
+    pub fn record_action(&mut self, fired: &AlertEvent, message: String) {
+        self.history.push(AlertEvent {
+            kind: AlertKind::Action,
+            message,
+            timestamp: unix_now(),
+            ..fired.clone()
+        });
+    }
+}
+
+fn event(
+    index: usize,
+    rule: &AlertRule,
+    kind: AlertKind,
+    message: String,
+    measurement: Option<&Measurement>,
+) -> AlertEvent {
+    AlertEvent {
+        rule: rule.name.clone(),
+        rule_index: index,
+        kind,
+        message,
+        pid: measurement.and_then(|m| m.process).map(|(pid, _)| pid),
+        value: measurement.map(|m| m.value),
+        timestamp: unix_now(),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Folder snapshot actions write to unless a rule names its own
+pub fn default_snapshot_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|mut path| {
+        path.push("TarnerMonitor");
+        path.push("snapshots");
+        path
+    })
+}
+
+/// Environment passed to command actions for the `fired` alert
+pub fn action_environment(
+    rule: &AlertRule,
+    fired: &AlertEvent,
+    process: Option<&ProcessInfo>,
+) -> Vec<(String, String)> {
+    let mut env = vec![
+        (String::from("TARNER_RULE"), rule.name.clone()),
+        (String::from("TARNER_MESSAGE"), fired.message.clone()),
+        (
+            String::from("TARNER_THRESHOLD"),
+            rule.condition.threshold().to_string(),
+        ),
+        (
+            String::from("TARNER_VALUE"),
+            fired
+                .value
+                .map_or_else(String::new, |v| format!("{:.2}", v)),
+        ),
+    ];
+    if let Some(pid) = fired.pid {
+        env.push((String::from("TARNER_PID"), pid.as_u32().to_string()));
+    }
+    if let Some(process) = process {
+        env.extend([
+            (
+                String::from("TARNER_PROCESS_NAME"),
+                process.name.to_string_lossy().into_owned(),
+            ),
+            (
+                String::from("TARNER_CPU"),
+                format!("{:.2}", process.cpu_usage),
+            ),
+            (
+                String::from("TARNER_MEMORY"),
+                process.memory_usage.to_string(),
+            ),
+            (
+                String::from("TARNER_COMMAND_LINE"),
+                process.command_line.clone(),
+            ),
+        ]);
+    }
+    env
+}
@@ -0,0 +1,124 @@
+use crate::state::Message;
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Theme, mouse};
+
+pub const CPU_COLOR: Color = Color::from_rgb(0.3, 0.6, 1.0);
+pub const MEMORY_COLOR: Color = Color::from_rgb(0.2, 0.8, 0.4);
+pub const READ_COLOR: Color = Color::from_rgb(1.0, 0.7, 0.2);
+pub const WRITE_COLOR: Color = Color::from_rgb(0.9, 0.3, 0.5);
+pub const SWAP_COLOR: Color = Color::from_rgb(0.7, 0.5, 1.0);
+
+const PALETTE: [Color; 8] = [
+    Color::from_rgb(0.3, 0.6, 1.0),
+    Color::from_rgb(0.2, 0.8, 0.4),
+    Color::from_rgb(1.0, 0.7, 0.2),
+    Color::from_rgb(0.9, 0.3, 0.5),
+    Color::from_rgb(0.7, 0.5, 1.0),
+    Color::from_rgb(0.2, 0.8, 0.8),
+    Color::from_rgb(0.9, 0.9, 0.3),
+    Color::from_rgb(1.0, 0.5, 0.3),
+];
+
+/// Distinct colour for the `index`-th line of a multi-series chart
+pub fn series_color(index: usize) -> Color {
+    PALETTE[index % PALETTE.len()]
+}
+
+struct Series {
+    values: Vec<f32>,
+    color: Color,
+}
+
+/// Line chart drawn on a canvas with the newest sample at the right edge
+pub struct LineChart {
+    series: Vec<Series>,
+    max: Option<f32>,
+    slots: usize,
+}
+
+impl LineChart {
+    /// A chart with room for `slots` samples across its width
+    pub fn new(slots: usize) -> Self {
+        LineChart {
+            series: Vec::new(),
+            max: None,
+            slots: slots.max(2),
+        }
+    }
+
+    pub fn series(mut self, values: Vec<f32>, color: Color) -> Self {
+        self.series.push(Series { values, color });
+        self
+    }
+
+    /// Fix the top of the y axis; otherwise it scales to the largest value
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn view<'a>(self, width: Length, height: Length) -> Element<'a, Message> {
+        Canvas::new(self).width(width).height(height).into()
+    }
+}
+
+impl canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        frame.fill_rectangle(
+            Point::ORIGIN,
+            bounds.size(),
+            theme.extended_palette().background.weak.color,
+        );
+
+        let max = self.max.unwrap_or_else(|| {
+            self.series
+                .iter()
+                .flat_map(|s| s.values.iter().copied())
+                .fold(0.0, f32::max)
+        });
+        let max = if max > 0.0 { max } else { 1.0 };
+        let step = bounds.width / (self.slots - 1) as f32;
+
+        for series in &self.series {
+            let shown = series.values.len().min(self.slots);
+            let skip = series.values.len() - shown;
+            let offset = self.slots - shown;
+
+            let path = Path::new(|builder| {
+                for (i, value) in series.values.iter().skip(skip).enumerate() {
+                    let x = (offset + i) as f32 * step;
+                    let y = bounds.height - (value / max).clamp(0.0, 1.0) * bounds.height;
+                    if i == 0 {
+                        builder.move_to(Point::new(x, y));
+                    } else {
+                        builder.line_to(Point::new(x, y));
+                    }
+                }
+            });
+            frame.stroke(
+                &path,
+                Stroke::default().with_color(series.color).with_width(1.5),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Small inline chart of the most recent samples, for list rows
+pub fn sparkline<'a>(values: Vec<f32>, max: f32, color: Color) -> Element<'a, Message> {
+    LineChart::new(60)
+        .series(values, color)
+        .max(max)
+        .view(Length::Fixed(80.0), Length::Fixed(18.0))
+}
@@ -0,0 +1,389 @@
+//! Headless command-line mode.
+//!
+//! With a subcommand the binary prints to stdout instead of opening the GUI,
+//! using the same SystemManager, filter queries, sort keys and CSV export as
+//! the Processes tab:
+//!
+//! ```text
+//! Tarner-Monitor list --sort cpu --filter "user:root mem>100MB" --format json
+//! Tarner-Monitor kill 1234 --signal TERM
+//! Tarner-Monitor info 1234
+//! Tarner-Monitor system
+//! ```
+//!
+//! `--tui` starts the terminal frontend in tui.rs instead of the GUI.
+
+use crate::columns::{self, ProcessColumn};
+use crate::format::{format_duration, format_megabytes, format_unix_time};
+use crate::process::ProcessInfo;
+use crate::query::{Query, QueryContext, SearchOptions};
+use crate::sort;
+use crate::state::write_csv;
+use crate::system::{KillOutcome, KillSignal, SystemManager};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{Value, json};
+use std::io::Write;
+use sysinfo::Pid;
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    args_conflicts_with_subcommands = true,
+    about = "System and process monitor; runs the GUI unless a subcommand is given"
+)]
+pub struct Cli {
+    /// Run the terminal UI instead of the GUI, e.g. over SSH
+    #[arg(long)]
+    pub tui: bool,
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// List processes, optionally filtered and sorted
+    List {
+        /// Filter query, as typed in the search box (e.g. "cpu>20 user:root")
+        #[arg(long)]
+        filter: Option<String>,
+        /// Treat the filter as one regular expression
+        #[arg(long)]
+        regex: bool,
+        /// Match words, text fields and regexes case-sensitively
+        #[arg(long)]
+        case_sensitive: bool,
+        /// Search the command line and executable path as well as the name
+        #[arg(long)]
+        full_command: bool,
+        /// Comma-separated sort keys, each optionally suffixed with :asc or :desc
+        #[arg(long, default_value = "name")]
+        sort: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Show at most this many processes
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Send a signal to a process
+    Kill {
+        pid: u32,
+        /// Signal name, or number on Unix, e.g. TERM, KILL, HUP, 9
+        #[arg(long, default_value = "TERM")]
+        signal: String,
+    },
+    /// Show everything known about one process
+    Info {
+        pid: u32,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Show operating system, CPU, memory and load figures
+    System {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Run `command` against `manager`, writing the result to `out`
+pub fn run(
+    command: CliCommand,
+    manager: &mut SystemManager,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    match command {
+        CliCommand::List {
+            filter,
+            regex,
+            case_sensitive,
+            full_command,
+            sort: sort_spec,
+            format,
+            limit,
+        } => {
+            let options = SearchOptions {
+                regex,
+                case_sensitive,
+                full_command,
+            };
+            let query = Query::parse_with(filter.as_deref().unwrap_or(""), options)
+                .map_err(|e| format!("invalid filter: {}", e))?;
+            let keys = sort::parse_keys(&sort_spec)?;
+            let context = context(manager);
+
+            let mut processes: Vec<ProcessInfo> = manager
+                .get_processes()
+                .into_iter()
+                .filter(|p| query.matches(p, context))
+                .collect();
+            processes.sort_by(|a, b| sort::compare(&keys, a, b));
+            processes.truncate(limit.unwrap_or(usize::MAX));
+            write_processes(out, &processes, format, context)
+        }
+        CliCommand::Kill { pid, signal } => {
+            let signal = KillSignal::from_name(&signal)
+                .ok_or_else(|| format!("unknown signal '{}'", signal))?;
+            let process = find(manager, pid)?;
+            match manager.kill_process(process.pid, signal) {
+                KillOutcome::Sent => writeln!(
+                    out,
+                    "Sent {} to {} (PID {})",
+                    signal,
+                    process.name.to_string_lossy(),
+                    pid
+                )
+                .map_err(|e| e.to_string()),
+                outcome => Err(format!(
+                    "could not send {} to PID {}: {}",
+                    signal, pid, outcome
+                )),
+            }
+        }
+        CliCommand::Info { pid, format } => {
+            let process = find(manager, pid)?;
+            let context = context(manager);
+            match format {
+                OutputFormat::Table => {
+                    let rows: Vec<Vec<String>> = process_fields(&process, context)
+                        .into_iter()
+                        .map(|(field, value)| {
+                            let value = match value {
+                                Value::String(text) => text,
+                                Value::Null => String::from("N/A"),
+                                other => other.to_string(),
+                            };
+                            vec![format!("{}:", field), value]
+                        })
+                        .collect();
+                    write_table(out, &rows)
+                }
+                format => write_processes(out, &[process], format, context),
+            }
+        }
+        CliCommand::System { format } => write_system(out, manager, format),
+    }
+}
+
+fn context(manager: &SystemManager) -> QueryContext {
+    QueryContext {
+        cpu_cores: manager.cpu_cores,
+        total_memory: manager.total_memory,
+    }
+}
+
+fn find(manager: &SystemManager, pid: u32) -> Result<ProcessInfo, String> {
+    manager
+        .get_processes()
+        .into_iter()
+        .find(|p| p.pid == Pid::from_u32(pid))
+        .ok_or_else(|| format!("no process with PID {}", pid))
+}
+
+fn write_processes(
+    out: &mut impl Write,
+    processes: &[ProcessInfo],
+    format: OutputFormat,
+    context: QueryContext,
+) -> Result<(), String> {
+    match format {
+        OutputFormat::Csv => write_csv(out, processes, context.cpu_cores, context.total_memory),
+        OutputFormat::Json => {
+            let list: Vec<Value> = processes
+                .iter()
+                .map(|p| {
+                    let fields = process_fields(p, context)
+                        .into_iter()
+                        .map(|(field, value)| (field.to_string(), value));
+                    Value::Object(fields.collect())
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut *out, &list).map_err(|e| e.to_string())?;
+            writeln!(out).map_err(|e| e.to_string())
+        }
+        OutputFormat::Table => {
+            // Same columns as a fresh Processes tab, name first and command last
+            let columns: Vec<ProcessColumn> = columns::default_columns()
+                .iter()
+                .map(|c| c.column)
+                .collect();
+            let mut rows = vec![
+                std::iter::once("Name".to_string())
+                    .chain(columns.iter().map(|c| c.label().to_string()))
+                    .collect::<Vec<_>>(),
+            ];
+            rows.extend(processes.iter().map(|p| {
+                std::iter::once(p.name.to_string_lossy().into_owned())
+                    .chain(
+                        columns
+                            .iter()
+                            .map(|c| c.value(p, context.cpu_cores, context.total_memory)),
+                    )
+                    .collect()
+            }));
+            write_table(out, &rows)
+        }
+    }
+}
+
+/// Write `rows` as left-aligned columns; the last column is not padded
+fn write_table(out: &mut impl Write, rows: &[Vec<String>]) -> Result<(), String> {
+    // Command lines may contain newlines, which would break the row apart
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.replace(['\n', '\r', '\t'], " "))
+                .collect()
+        })
+        .collect();
+    let columns = rows.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+    for row in &rows {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            if i + 1 == columns {
+                line.push_str(cell);
+            } else {
+                line.push_str(&format!("{:width$}  ", cell, width = widths[i]));
+            }
+        }
+        writeln!(out, "{}", line.trim_end()).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Every field of `process` with raw numbers, for JSON output and `info`
+fn process_fields(process: &ProcessInfo, context: QueryContext) -> Vec<(&'static str, Value)> {
+    let path = |path: &Option<std::path::PathBuf>| {
+        path.as_ref()
+            .map_or(Value::Null, |p| json!(p.display().to_string()))
+    };
+    vec![
+        ("pid", json!(process.pid.as_u32())),
+        ("ppid", json!(process.parent_pid.map(|p| p.as_u32()))),
+        ("name", json!(process.name.to_string_lossy())),
+        ("status", json!(process.status.to_string())),
+        ("user", json!(process.user)),
+        ("uid", json!(process.uid)),
+        ("gid", json!(process.gid)),
+        (
+            "cpu_percent",
+            json!(f64::from(process.cpu_usage) / context.cpu_cores.max(1) as f64),
+        ),
+        ("memory_bytes", json!(process.memory_usage)),
+        (
+            "memory_percent",
+            json!(process.memory_usage as f64 / context.total_memory.max(1) as f64 * 100.0),
+        ),
+        ("virtual_memory_bytes", json!(process.virtual_memory)),
+        ("disk_read_bytes", json!(process.disk_usage.read_bytes)),
+        ("disk_write_bytes", json!(process.disk_usage.written_bytes)),
+        ("runtime_secs", json!(process.run_time)),
+        ("start_time", json!(process.start_time)),
+        ("threads", json!(process.thread_count)),
+        ("exe", path(&process.exe)),
+        ("cwd", path(&process.cwd)),
+        ("command_line", json!(process.command_line)),
+    ]
+}
+
+fn write_system(
+    out: &mut impl Write,
+    manager: &SystemManager,
+    format: OutputFormat,
+) -> Result<(), String> {
+    let Some(sample) = manager.history().latest() else {
+        return Err(String::from("no system sample recorded yet"));
+    };
+    let load = sample.load_average;
+    match format {
+        OutputFormat::Json => {
+            let value = json!({
+                "os_name": manager.os_name,
+                "os_version": manager.os_version,
+                "kernel_version": manager.kernel_version,
+                "hostname": manager.hostname,
+                "cpu_brand": manager.cpu_brand,
+                "cpu_cores": manager.cpu_cores,
+                "cpu_percent": sample.cpu_usage,
+                "total_memory_bytes": manager.total_memory,
+                "used_memory_bytes": sample.used_memory,
+                "available_memory_bytes": sample.available_memory,
+                "total_swap_bytes": sample.total_swap,
+                "used_swap_bytes": sample.used_swap,
+                "load_average": [load.one, load.five, load.fifteen],
+                "uptime_secs": sample.uptime,
+                "boot_time": manager.boot_time,
+                "processes": sample.process_count,
+                "threads": sample.thread_count,
+            });
+            serde_json::to_writer_pretty(&mut *out, &value).map_err(|e| e.to_string())?;
+            writeln!(out).map_err(|e| e.to_string())
+        }
+        OutputFormat::Csv | OutputFormat::Table => {
+            let rows = [
+                ("OS", format!("{} {}", manager.os_name, manager.os_version)),
+                ("Kernel", manager.kernel_version.clone()),
+                ("Hostname", manager.hostname.clone()),
+                ("CPU", manager.cpu_brand.clone()),
+                ("Cores", manager.cpu_cores.to_string()),
+                ("CPU usage", format!("{:.1}%", sample.cpu_usage)),
+                (
+                    "Memory",
+                    format!(
+                        "{} / {}",
+                        format_megabytes(sample.used_memory),
+                        format_megabytes(manager.total_memory)
+                    ),
+                ),
+                (
+                    "Swap",
+                    format!(
+                        "{} / {}",
+                        format_megabytes(sample.used_swap),
+                        format_megabytes(sample.total_swap)
+                    ),
+                ),
+                (
+                    "Load average",
+                    format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen),
+                ),
+                ("Uptime", format_duration(sample.uptime)),
+                ("Boot time", format_unix_time(manager.boot_time)),
+                (
+                    "Tasks",
+                    format!(
+                        "{} processes, {} threads",
+                        sample.process_count, sample.thread_count
+                    ),
+                ),
+            ];
+            if format == OutputFormat::Csv {
+                let mut writer = csv::Writer::from_writer(&mut *out);
+                writer
+                    .write_record(["Field", "Value"])
+                    .map_err(|e| e.to_string())?;
+                for (field, value) in rows {
+                    writer
+                        .write_record([field, value.as_str()])
+                        .map_err(|e| e.to_string())?;
+                }
+                writer.flush().map_err(|e| e.to_string())
+            } else {
+                let rows: Vec<Vec<String>> = rows
+                    .into_iter()
+                    .map(|(field, value)| vec![format!("{}:", field), value])
+                    .collect();
+                write_table(out, &rows)
+            }
+        }
+    }
+}
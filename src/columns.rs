@@ -0,0 +1,178 @@
+use crate::format::{format_duration, format_megabytes, format_rate};
+use crate::process::ProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Narrowest a column can be resized to, in pixels
+pub const MIN_WIDTH: f32 = 40.0;
+/// Widest a column can be resized to, in pixels
+pub const MAX_WIDTH: f32 = 600.0;
+/// Pixels added or removed by one resize step
+pub const WIDTH_STEP: f32 = 20.0;
+
+/// Optional columns of the process table; the name column is always shown first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProcessColumn {
+    Pid,
+    ParentPid,
+    User,
+    Status,
+    Cpu,
+    Memory,
+    MemoryPercent,
+    VirtualMemory,
+    DiskRead,
+    DiskWrite,
+    Runtime,
+    Threads,
+    CommandLine,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 13] = [
+        ProcessColumn::Pid,
+        ProcessColumn::ParentPid,
+        ProcessColumn::User,
+        ProcessColumn::Status,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::MemoryPercent,
+        ProcessColumn::VirtualMemory,
+        ProcessColumn::DiskRead,
+        ProcessColumn::DiskWrite,
+        ProcessColumn::Runtime,
+        ProcessColumn::Threads,
+        ProcessColumn::CommandLine,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::ParentPid => "PPID",
+            ProcessColumn::User => "User",
+            ProcessColumn::Status => "Status",
+            ProcessColumn::Cpu => "CPU %",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::MemoryPercent => "Memory %",
+            ProcessColumn::VirtualMemory => "Virtual Memory",
+            ProcessColumn::DiskRead => "Disk Read",
+            ProcessColumn::DiskWrite => "Disk Write",
+            ProcessColumn::Runtime => "Runtime",
+            ProcessColumn::Threads => "Threads",
+            ProcessColumn::CommandLine => "Command",
+        }
+    }
+
+    pub fn default_width(self) -> f32 {
+        match self {
+            ProcessColumn::CommandLine => 300.0,
+            ProcessColumn::VirtualMemory
+            | ProcessColumn::Memory
+            | ProcessColumn::DiskRead
+            | ProcessColumn::DiskWrite
+            | ProcessColumn::Runtime => 110.0,
+            _ => 80.0,
+        }
+    }
+
+    /// Text shown in this column for `process`
+    pub fn value(self, process: &ProcessInfo, cpu_cores: usize, total_memory: u64) -> String {
+        match self {
+            ProcessColumn::Pid => process.pid.as_u32().to_string(),
+            ProcessColumn::ParentPid => process
+                .parent_pid
+                .map_or_else(|| "N/A".to_string(), |pid| pid.as_u32().to_string()),
+            ProcessColumn::User => process.user_label(),
+            ProcessColumn::Status => process.status.to_string(),
+            ProcessColumn::Cpu => format!("{:.2}", process.cpu_usage / cpu_cores.max(1) as f32),
+            ProcessColumn::Memory => format_megabytes(process.memory_usage),
+            ProcessColumn::MemoryPercent => format!(
+                "{:.2}",
+                process.memory_usage as f64 / total_memory.max(1) as f64 * 100.0
+            ),
+            ProcessColumn::VirtualMemory => format_megabytes(process.virtual_memory),
+            ProcessColumn::DiskRead => format_rate(process.disk_usage.read_bytes),
+            ProcessColumn::DiskWrite => format_rate(process.disk_usage.written_bytes),
+            ProcessColumn::Runtime => format_duration(process.run_time),
+            ProcessColumn::Threads => process.thread_count.to_string(),
+            ProcessColumn::CommandLine => process.command_line.clone(),
+        }
+    }
+
+    /// Order two processes by the raw value behind this column, ascending
+    pub fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            ProcessColumn::Pid => a.pid.cmp(&b.pid),
+            ProcessColumn::ParentPid => a.parent_pid.cmp(&b.parent_pid),
+            ProcessColumn::User => a.user_label().cmp(&b.user_label()),
+            ProcessColumn::Status => a.status.to_string().cmp(&b.status.to_string()),
+            ProcessColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessColumn::Memory | ProcessColumn::MemoryPercent => {
+                a.memory_usage.cmp(&b.memory_usage)
+            }
+            ProcessColumn::VirtualMemory => a.virtual_memory.cmp(&b.virtual_memory),
+            ProcessColumn::DiskRead => a.disk_usage.read_bytes.cmp(&b.disk_usage.read_bytes),
+            ProcessColumn::DiskWrite => a.disk_usage.written_bytes.cmp(&b.disk_usage.written_bytes),
+            ProcessColumn::Runtime => a.run_time.cmp(&b.run_time),
+            ProcessColumn::Threads => a.thread_count.cmp(&b.thread_count),
+            ProcessColumn::CommandLine => a.command_line.cmp(&b.command_line),
+        }
+    }
+}
+
+/// A visible column and its width in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub column: ProcessColumn,
+    pub width: f32,
+}
+
+impl ColumnLayout {
+    pub fn new(column: ProcessColumn) -> Self {
+        ColumnLayout {
+            column,
+            width: column.default_width(),
+        }
+    }
+}
+
+/// Columns shown until the user picks their own
+pub fn default_columns() -> Vec<ColumnLayout> {
+    [
+        ProcessColumn::Pid,
+        ProcessColumn::Cpu,
+        ProcessColumn::MemoryPercent,
+        ProcessColumn::User,
+        ProcessColumn::Threads,
+        ProcessColumn::CommandLine,
+    ]
+    .into_iter()
+    .map(ColumnLayout::new)
+    .collect()
+}
+
+/// Show `column` at the end of the table, or hide it if it is already shown
+pub fn toggle(columns: &mut Vec<ColumnLayout>, column: ProcessColumn) {
+    if let Some(index) = columns.iter().position(|c| c.column == column) {
+        columns.remove(index);
+    } else {
+        columns.push(ColumnLayout::new(column));
+    }
+}
+
+/// Move `column` by `offset` positions, stopping at either end of the table
+pub fn move_column(columns: &mut Vec<ColumnLayout>, column: ProcessColumn, offset: isize) {
+    let Some(index) = columns.iter().position(|c| c.column == column) else {
+        return;
+    };
+    let layout = columns.remove(index);
+    let target = index.saturating_add_signed(offset).min(columns.len());
+    columns.insert(target, layout);
+}
+
+/// Change the width of `column` by `delta` pixels within the allowed range
+pub fn resize(columns: &mut [ColumnLayout], column: ProcessColumn, delta: f32) {
+    if let Some(layout) = columns.iter_mut().find(|c| c.column == column) {
+        layout.width = (layout.width + delta).clamp(MIN_WIDTH, MAX_WIDTH);
+    }
+}
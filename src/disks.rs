@@ -0,0 +1,85 @@
+use crate::history::RingBuffer;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// One mounted filesystem
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskInfo {
+    pub name: String,
+    pub mount_point: PathBuf,
+    pub file_system: String,
+    pub total_space: u64,
+    pub available_space: u64,
+    pub is_removable: bool,
+    /// Bytes read since the previous refresh
+    pub read_bytes: u64,
+    /// Bytes written since the previous refresh
+    pub written_bytes: u64,
+}
+
+impl DiskInfo {
+    pub fn used_space(&self) -> u64 {
+        self.total_space.saturating_sub(self.available_space)
+    }
+
+    /// Used space in percent of the total, 0 for filesystems that report no size
+    pub fn usage_percent(&self) -> f32 {
+        if self.total_space == 0 {
+            0.0
+        } else {
+            self.used_space() as f32 / self.total_space as f32 * 100.0
+        }
+    }
+}
+
+/// Read and write volume of a disk during one refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskIoSample {
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+}
+
+/// Latest mount list plus per-mount I/O history
+pub struct DiskManager {
+    disks: Vec<DiskInfo>,
+    capacity: usize,
+    history: HashMap<PathBuf, RingBuffer<DiskIoSample>>,
+}
+
+impl DiskManager {
+    pub fn new(capacity: usize) -> Self {
+        DiskManager {
+            disks: Vec::new(),
+            capacity,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Replace the mount list, record one I/O sample per mount and forget unmounted ones
+    pub fn update(&mut self, mut disks: Vec<DiskInfo>) {
+        disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+
+        let mounted: HashSet<&Path> = disks.iter().map(|d| d.mount_point.as_path()).collect();
+        self.history
+            .retain(|mount_point, _| mounted.contains(mount_point.as_path()));
+        for disk in &disks {
+            self.history
+                .entry(disk.mount_point.clone())
+                .or_insert_with(|| RingBuffer::new(self.capacity))
+                .push(DiskIoSample {
+                    read_bytes: disk.read_bytes,
+                    written_bytes: disk.written_bytes,
+                });
+        }
+        self.disks = disks;
+    }
+
+    /// Mounted filesystems ordered by mount point
+    pub fn disks(&self) -> &[DiskInfo] {
+        &self.disks
+    }
+
+    pub fn history(&self, mount_point: &Path) -> Option<&RingBuffer<DiskIoSample>> {
+        self.history.get(mount_point)
+    }
+}
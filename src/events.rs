@@ -0,0 +1,120 @@
+use crate::history::RingBuffer;
+use crate::process::ProcessInfo;
+use crate::query::{Query, QueryContext, SearchOptions};
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::Pid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleKind {
+    Started,
+    Exited,
+}
+
+impl fmt::Display for LifecycleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleKind::Started => write!(f, "Started"),
+            LifecycleKind::Exited => write!(f, "Exited"),
+        }
+    }
+}
+
+/// A process appearing or disappearing between two refreshes
+#[derive(Debug, Clone)]
+pub struct ProcessEvent {
+    pub kind: LifecycleKind,
+    /// The process as first seen when started, or as last seen before it exited
+    pub process: ProcessInfo,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Started and exited processes found by diffing consecutive snapshots
+pub struct EventFeed {
+    events: RingBuffer<ProcessEvent>,
+}
+
+impl EventFeed {
+    pub const CAPACITY: usize = 1000;
+
+    pub fn new() -> Self {
+        EventFeed {
+            events: RingBuffer::new(Self::CAPACITY),
+        }
+    }
+
+    /// Compare two snapshots and record what started and exited, exits first.
+    /// A PID reused by a new process counts as one exit and one start.
+    pub fn record(
+        &mut self,
+        previous: &[ProcessInfo],
+        current: &[ProcessInfo],
+    ) -> Vec<ProcessEvent> {
+        let identity = |p: &ProcessInfo| (p.pid, p.start_time);
+        let before: HashSet<(Pid, u64)> = previous.iter().map(identity).collect();
+        let after: HashSet<(Pid, u64)> = current.iter().map(identity).collect();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let event = |kind, process: &ProcessInfo| ProcessEvent {
+            kind,
+            process: process.clone(),
+            timestamp,
+        };
+
+        let mut events: Vec<ProcessEvent> = previous
+            .iter()
+            .filter(|p| !after.contains(&identity(p)))
+            .map(|p| event(LifecycleKind::Exited, p))
+            .collect();
+        events.extend(
+            current
+                .iter()
+                .filter(|p| !before.contains(&identity(p)))
+                .map(|p| event(LifecycleKind::Started, p)),
+        );
+
+        for event in &events {
+            self.events.push(event.clone());
+        }
+        events
+    }
+
+    /// All recorded events, oldest first
+    pub fn events(&self) -> &RingBuffer<ProcessEvent> {
+        &self.events
+    }
+
+    /// The query an events filter applies; an invalid query falls back to a
+    /// plain name search
+    pub fn parse_filter(filter: &str) -> Query {
+        Query::parse(filter).unwrap_or_else(|_| Query::literal(filter, SearchOptions::default()))
+    }
+
+    /// Events of `kind` (or any kind) whose process matches `query`, newest first
+    pub fn filtered(
+        &self,
+        query: &Query,
+        kind: Option<LifecycleKind>,
+        context: QueryContext,
+    ) -> Vec<&ProcessEvent> {
+        self.events
+            .iter()
+            .rev()
+            .filter(|e| kind.is_none_or(|kind| e.kind == kind))
+            .filter(|e| query.matches(&e.process, context))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.events = RingBuffer::new(Self::CAPACITY);
+    }
+}
+
+impl Default for EventFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::state::REFRESH_INTERVAL;
+use std::fmt::Display;
+
+/// Format a byte count in MB with one decimal
+pub fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+/// Format an optional value, showing `N/A` when it is missing
+pub fn format_optional<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "N/A".to_string(), |v| v.to_string())
+}
+
+/// Format bytes moved during one refresh as a per-second rate
+pub fn format_rate(bytes: u64) -> String {
+    format!(
+        "{:.1} KB/s",
+        bytes as f64 / 1024.0 / REFRESH_INTERVAL.as_secs_f64()
+    )
+}
+
+/// Format a number of seconds as `3d 04:12:05`
+pub fn format_duration(secs: u64) -> String {
+    let days = secs / 86_400;
+    let (hours, minutes, seconds) = (secs % 86_400 / 3600, secs % 3600 / 60, secs % 60);
+    if days > 0 {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+/// Format seconds since the Unix epoch as a UTC date and time
+pub fn format_unix_time(secs: u64) -> String {
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let time = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
@@ -0,0 +1,190 @@
+use crate::process::ProcessInfo;
+use std::collections::{HashMap, HashSet, VecDeque};
+use sysinfo::Pid;
+
+/// Fixed-capacity buffer that drops the oldest sample when full
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    samples: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            samples: VecDeque::with_capacity(capacity.min(4096)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, sample: T) {
+        while self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Change the capacity, discarding the oldest samples if it shrinks
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn latest(&self) -> Option<&T> {
+        self.samples.back()
+    }
+
+    /// Samples from oldest to newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    /// The newest `count` samples, oldest first
+    pub fn last_n(&self, count: usize) -> impl Iterator<Item = &T> {
+        self.samples
+            .iter()
+            .skip(self.samples.len().saturating_sub(count))
+    }
+}
+
+/// One refresh worth of resource usage for a process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessSample {
+    pub cpu_usage: f32,
+    pub memory_usage: u64,
+    /// Bytes read since the previous refresh
+    pub disk_read: u64,
+    /// Bytes written since the previous refresh
+    pub disk_written: u64,
+}
+
+impl From<&ProcessInfo> for ProcessSample {
+    fn from(process: &ProcessInfo) -> Self {
+        ProcessSample {
+            cpu_usage: process.cpu_usage,
+            memory_usage: process.memory_usage,
+            disk_read: process.disk_usage.read_bytes,
+            disk_written: process.disk_usage.written_bytes,
+        }
+    }
+}
+
+/// Per-PID resource history, bounded to a fixed number of samples
+pub struct ProcessHistory {
+    capacity: usize,
+    /// Samples keyed by PID, tagged with the start time of the process they belong to
+    per_pid: HashMap<Pid, (u64, RingBuffer<ProcessSample>)>,
+}
+
+impl ProcessHistory {
+    pub fn new(capacity: usize) -> Self {
+        ProcessHistory {
+            capacity,
+            per_pid: HashMap::new(),
+        }
+    }
+
+    /// Append a sample for every listed process and forget PIDs that have exited.
+    /// A PID reused by a new process starts over with an empty history.
+    pub fn record(&mut self, processes: &[ProcessInfo]) {
+        let alive: HashSet<Pid> = processes.iter().map(|p| p.pid).collect();
+        self.per_pid.retain(|pid, _| alive.contains(pid));
+        for process in processes {
+            let (start_time, buffer) = self
+                .per_pid
+                .entry(process.pid)
+                .or_insert_with(|| (process.start_time, RingBuffer::new(self.capacity)));
+            if *start_time != process.start_time {
+                *start_time = process.start_time;
+                *buffer = RingBuffer::new(self.capacity);
+            }
+            buffer.push(ProcessSample::from(process));
+        }
+    }
+
+    pub fn get(&self, pid: Pid) -> Option<&RingBuffer<ProcessSample>> {
+        self.per_pid.get(&pid).map(|(_, buffer)| buffer)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        for (_, buffer) in self.per_pid.values_mut() {
+            buffer.set_capacity(capacity);
+        }
+    }
+}
+
+/// One refresh worth of system-wide usage
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemSample {
+    pub cpu_usage: f32,
+    pub core_usages: Vec<f32>,
+    /// Frequency in MHz of each logical core
+    pub core_frequencies: Vec<u64>,
+    pub used_memory: u64,
+    pub available_memory: u64,
+    pub used_swap: u64,
+    pub total_swap: u64,
+    pub load_average: LoadAverage,
+    /// Seconds since boot
+    pub uptime: u64,
+    pub process_count: usize,
+    pub thread_count: usize,
+}
+
+/// 1, 5 and 15 minute load averages
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// Direction a metric moved between two samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Up,
+    Down,
+    Steady,
+}
+
+impl Trend {
+    /// Changes smaller than this count as steady so rounding noise does not flicker
+    const TOLERANCE: f64 = 0.005;
+
+    pub fn between(previous: f64, current: f64) -> Trend {
+        if current - previous > Self::TOLERANCE {
+            Trend::Up
+        } else if previous - current > Self::TOLERANCE {
+            Trend::Down
+        } else {
+            Trend::Steady
+        }
+    }
+
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Trend::Up => "▲",
+            Trend::Down => "▼",
+            Trend::Steady => "▶",
+        }
+    }
+}
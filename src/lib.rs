@@ -1,5 +1,22 @@
+pub mod alerts;
+pub mod chart;
+pub mod cli;
+pub mod columns;
+pub mod disks;
+pub mod events;
+pub mod format;
+pub mod history;
 pub mod logger;
+pub mod network;
+pub mod presets;
 pub mod process;
+pub mod query;
+pub mod sensors;
+pub mod sort;
+pub mod source;
 pub mod state;
 pub mod system;
+pub mod tree;
+pub mod tui;
 pub mod view;
+pub mod watchdog;
@@ -1,5 +1,6 @@
 use simplelog::{
-    ColorChoice, CombinedLogger, Config, LevelFilter, TermLogger, TerminalMode, WriteLogger,
+    ColorChoice, CombinedLogger, Config, LevelFilter, SharedLogger, TermLogger, TerminalMode,
+    WriteLogger,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -13,6 +14,15 @@ pub fn get_log_path() -> Option<PathBuf> {
 }
 
 pub fn init_logging() -> Result<(), String> {
+    init(true)
+}
+
+/// Log to the file only; terminal output would garble the terminal UI
+pub fn init_file_logging() -> Result<(), String> {
+    init(false)
+}
+
+fn init(to_terminal: bool) -> Result<(), String> {
     let Some(log_path) = get_log_path() else {
         return Err("Could not find config directory to create log file.".to_string());
     };
@@ -30,16 +40,21 @@ pub fn init_logging() -> Result<(), String> {
         .map_err(|e| format!("Failed to create log file at {:?}: {}", log_path, e))?;
 
     // Configure the loggers
-    CombinedLogger::init(vec![
+    let mut loggers: Vec<Box<dyn SharedLogger>> = Vec::new();
+    if to_terminal {
         // Log to the terminal (for debugging with `cargo run`)
-        TermLogger::new(
+        loggers.push(TermLogger::new(
             LevelFilter::Info,
             Config::default(),
             TerminalMode::Mixed,
             ColorChoice::Auto,
-        ),
-        // Log to the file
-        WriteLogger::new(LevelFilter::Info, Config::default(), log_file),
-    ])
-    .map_err(|e| format!("Failed to initialize logger: {}", e))
+        ));
+    }
+    // Log to the file
+    loggers.push(WriteLogger::new(
+        LevelFilter::Info,
+        Config::default(),
+        log_file,
+    ));
+    CombinedLogger::init(loggers).map_err(|e| format!("Failed to initialize logger: {}", e))
 }
@@ -1,11 +1,31 @@
-mod logger;
-mod process;
-mod state;
-mod system;
-mod view;
-use state::TarnerMonitor;
+use clap::Parser;
+use tarner_monitor::cli::{self, Cli};
+use tarner_monitor::logger;
+use tarner_monitor::state::TarnerMonitor;
+use tarner_monitor::system::SystemManager;
+use tarner_monitor::tui::Tui;
 
 fn main() -> iced::Result {
-    logger::init_logging().expect("Failed to initialize logger");
-    TarnerMonitor::run_with_settings()
+    let cli = Cli::parse();
+    if cli.tui {
+        logger::init_file_logging().expect("Failed to initialize logger");
+        if let Err(e) = Tui::new(TarnerMonitor::new()).run() {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    let Some(command) = cli.command else {
+        logger::init_logging().expect("Failed to initialize logger");
+        return TarnerMonitor::run_with_settings();
+    };
+
+    logger::init_file_logging().expect("Failed to initialize logger");
+    let mut manager = SystemManager::new();
+
+    if let Err(e) = cli::run(command, &mut manager, &mut std::io::stdout().lock()) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    Ok(())
 }
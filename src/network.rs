@@ -0,0 +1,73 @@
+use crate::history::RingBuffer;
+use std::collections::{HashMap, HashSet};
+
+/// One network interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInfo {
+    pub name: String,
+    pub mac_address: String,
+    /// Addresses with their prefix length, e.g. `192.168.1.10/24`
+    pub ip_addresses: Vec<String>,
+    /// Bytes received since the previous refresh
+    pub received: u64,
+    /// Bytes transmitted since the previous refresh
+    pub transmitted: u64,
+    pub total_received: u64,
+    pub total_transmitted: u64,
+    pub total_packets_received: u64,
+    pub total_packets_transmitted: u64,
+    pub total_errors_received: u64,
+    pub total_errors_transmitted: u64,
+}
+
+/// Traffic of an interface during one refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkSample {
+    pub received: u64,
+    pub transmitted: u64,
+}
+
+/// Latest interface list plus per-interface traffic history
+pub struct NetworkManager {
+    interfaces: Vec<NetworkInfo>,
+    capacity: usize,
+    history: HashMap<String, RingBuffer<NetworkSample>>,
+}
+
+impl NetworkManager {
+    pub fn new(capacity: usize) -> Self {
+        NetworkManager {
+            interfaces: Vec::new(),
+            capacity,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Replace the interface list, record one sample per interface and forget removed ones
+    pub fn update(&mut self, mut interfaces: Vec<NetworkInfo>) {
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let present: HashSet<&str> = interfaces.iter().map(|i| i.name.as_str()).collect();
+        self.history
+            .retain(|name, _| present.contains(name.as_str()));
+        for interface in &interfaces {
+            self.history
+                .entry(interface.name.clone())
+                .or_insert_with(|| RingBuffer::new(self.capacity))
+                .push(NetworkSample {
+                    received: interface.received,
+                    transmitted: interface.transmitted,
+                });
+        }
+        self.interfaces = interfaces;
+    }
+
+    /// Interfaces ordered by name
+    pub fn interfaces(&self) -> &[NetworkInfo] {
+        &self.interfaces
+    }
+
+    pub fn history(&self, name: &str) -> Option<&RingBuffer<NetworkSample>> {
+        self.history.get(name)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::columns::{self, ColumnLayout, ProcessColumn};
+use crate::query::SearchOptions;
+use crate::sort::{self, SortField, SortKey};
+use serde::{Deserialize, Serialize};
+
+/// A named search saved together with the sort and columns it was used with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub search: String,
+    #[serde(default)]
+    pub search_options: SearchOptions,
+    #[serde(default = "sort::default_sort")]
+    pub sort: Vec<SortKey>,
+    #[serde(default = "columns::default_columns")]
+    pub columns: Vec<ColumnLayout>,
+}
+
+/// Presets offered until the user saves their own
+pub fn default_presets() -> Vec<FilterPreset> {
+    vec![
+        FilterPreset {
+            name: String::from("Heavy hitters"),
+            search: String::from("cpu>20 OR mem>10%"),
+            search_options: SearchOptions::default(),
+            sort: vec![
+                SortKey::descending(SortField::Column(ProcessColumn::Cpu)),
+                SortKey::descending(SortField::Column(ProcessColumn::Memory)),
+            ],
+            columns: columns::default_columns(),
+        },
+        FilterPreset {
+            name: String::from("Zombies"),
+            search: String::from("status:zombie"),
+            search_options: SearchOptions::default(),
+            sort: vec![SortKey::ascending(SortField::Column(
+                ProcessColumn::ParentPid,
+            ))],
+            columns: [
+                ProcessColumn::Pid,
+                ProcessColumn::ParentPid,
+                ProcessColumn::User,
+                ProcessColumn::Status,
+                ProcessColumn::Runtime,
+            ]
+            .into_iter()
+            .map(ColumnLayout::new)
+            .collect(),
+        },
+    ]
+}
+
+/// Preset names are matched ignoring ASCII case everywhere
+fn same_name(preset: &FilterPreset, name: &str) -> bool {
+    preset.name.eq_ignore_ascii_case(name)
+}
+
+/// The preset called `name`
+pub fn find<'a>(presets: &'a [FilterPreset], name: &str) -> Option<&'a FilterPreset> {
+    presets.iter().find(|p| same_name(p, name))
+}
+
+/// Add `preset`, replacing any existing preset with the same name
+pub fn save(presets: &mut Vec<FilterPreset>, preset: FilterPreset) {
+    match presets.iter_mut().find(|p| same_name(p, &preset.name)) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+}
+
+/// Remove the preset called `name`, returning whether one was found
+pub fn remove(presets: &mut Vec<FilterPreset>, name: &str) -> bool {
+    let before = presets.len();
+    presets.retain(|p| !same_name(p, name));
+    presets.len() != before
+}
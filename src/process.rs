@@ -1,4 +1,5 @@
 use std::ffi::OsString;
+use std::path::PathBuf;
 use sysinfo::DiskUsage;
 use sysinfo::Pid;
 use sysinfo::ProcessStatus;
@@ -10,13 +11,30 @@ pub struct ProcessInfo {
     pub pid: Pid,
     pub cpu_usage: f32,
     pub memory_usage: u64,
+    pub virtual_memory: u64,
     pub run_time: u64,
     pub status: ProcessStatus,
     pub acc_cpu_time: u64,
     pub disk_usage: DiskUsage,
+    /// Full command line, arguments separated by spaces; empty when it cannot be read
+    pub command_line: String,
+    /// Command-line arguments, program first; empty when they cannot be read
+    pub args: Vec<String>,
+    pub exe: Option<PathBuf>,
+    pub cwd: Option<PathBuf>,
+    /// Name of the owning user, if it could be resolved from the UID
+    pub user: Option<String>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+    pub thread_count: usize,
+    /// A thread that the platform lists alongside processes (Linux tasks)
+    pub is_thread: bool,
 }
 
 impl ProcessInfo {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: OsString,
         parent_pid: Option<Pid>,
@@ -34,10 +52,30 @@ impl ProcessInfo {
             pid,
             cpu_usage,
             memory_usage,
+            virtual_memory: 0,
             run_time,
             status,
             acc_cpu_time,
             disk_usage,
+            command_line: String::new(),
+            args: Vec::new(),
+            exe: None,
+            cwd: None,
+            user: None,
+            uid: None,
+            gid: None,
+            start_time: 0,
+            thread_count: 1,
+            is_thread: false,
+        }
+    }
+
+    /// Owning user name, falling back to the numeric UID
+    pub fn user_label(&self) -> String {
+        match (&self.user, self.uid) {
+            (Some(user), _) => user.clone(),
+            (None, Some(uid)) => uid.to_string(),
+            (None, None) => String::from("N/A"),
         }
     }
 }
@@ -0,0 +1,645 @@
+//! Filter expressions for the process search box.
+//!
+//! A query is a list of terms joined by `AND` (the default), `OR` and `NOT`,
+//! with parentheses for grouping. A term is either a bare word, which matches
+//! process names case-insensitively by default, or `field<op>value`:
+//!
+//! * `cpu>20`, `mem>500MB`, `mem>=10%`, `pid:1234`, `ppid=1`, `threads>8`
+//! * `user:root`, `status:zombie`, `name:ssh`, `cmd:manage.py`
+//! * `name~^ssh`, `cmd~--port=\d+` (regular expressions)
+//!
+//! Numeric fields take `>`, `>=`, `<`, `<=`, `=`/`:` and `!=`. Text fields take
+//! `:` (substring for name and cmd, exact for user and status), `=`, `!=` and `~`.
+//!
+//! [`SearchOptions`] change how terms match: `case_sensitive` applies to bare
+//! words and text fields alike, `full_command` also searches bare words in the
+//! command line and executable path, and with `regex` set the whole input is
+//! one regular expression instead of a query.
+
+use crate::process::ProcessInfo;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How free text in the search box is matched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    /// Treat the whole input as a regular expression instead of a query
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Also search the full command line and executable path, not just the name
+    pub full_command: bool,
+}
+
+impl SearchOptions {
+    /// Short description of the active mode for display next to the search box
+    pub fn describe(self) -> String {
+        format!(
+            "{}, {}, {}",
+            if self.regex { "regex" } else { "query" },
+            if self.case_sensitive {
+                "case-sensitive"
+            } else {
+                "ignoring case"
+            },
+            if self.full_command {
+                "name + command + path"
+            } else {
+                "name only"
+            }
+        )
+    }
+
+    fn build(self, pattern: &str) -> Result<Condition, regex::Error> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .map(|pattern| Condition::Search {
+                pattern,
+                full_command: self.full_command,
+            })
+    }
+
+    fn literal(self, text: &str) -> Condition {
+        self.build(&regex::escape(text))
+            .expect("an escaped pattern is always a valid regex")
+    }
+}
+
+/// Why a query could not be parsed, with the character offset of the problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.position + 1)
+    }
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone)]
+pub enum Query {
+    Term(Condition),
+    Not(Box<Query>),
+    /// Matches when every part matches; an empty list matches everything
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+    /// Bare word or regex-mode input, matched as set by [`SearchOptions`]
+    Search {
+        pattern: Regex,
+        full_command: bool,
+    },
+    Numeric(NumericField, Comparison, f64),
+    /// `field:value` and friends; without `case_sensitive` the needle is stored lowercased
+    Text {
+        field: TextField,
+        text_match: TextMatch,
+        case_sensitive: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericField {
+    /// Share of total CPU in percent, as shown in the list
+    Cpu,
+    /// Resident memory in bytes
+    Memory,
+    /// Resident memory in percent of total memory
+    MemoryPercent,
+    Pid,
+    ParentPid,
+    Threads,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    Name,
+    Command,
+    User,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone)]
+pub enum TextMatch {
+    Contains(String),
+    Equals(String),
+    NotEquals(String),
+    Regex(Regex),
+}
+
+/// Totals needed to turn raw usage into the percentages shown in the list
+#[derive(Debug, Clone, Copy)]
+pub struct QueryContext {
+    pub cpu_cores: usize,
+    pub total_memory: u64,
+}
+
+impl Query {
+    /// Parse `input` with the default search options
+    pub fn parse(input: &str) -> Result<Query, ParseError> {
+        Query::parse_with(input, SearchOptions::default())
+    }
+
+    /// Parse `input`; an empty or all-whitespace query matches every process
+    pub fn parse_with(input: &str, options: SearchOptions) -> Result<Query, ParseError> {
+        if options.regex {
+            return if input.trim().is_empty() {
+                Ok(Query::And(Vec::new()))
+            } else {
+                options
+                    .build(input)
+                    .map(Query::Term)
+                    .map_err(|e| ParseError {
+                        message: format!("invalid regular expression: {}", e),
+                        position: 0,
+                    })
+            };
+        }
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            index: 0,
+            end: input.chars().count(),
+            options,
+        };
+        if parser.tokens.is_empty() {
+            return Ok(Query::And(Vec::new()));
+        }
+        let query = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(ParseError {
+                message: format!("unexpected '{}'", token.text()),
+                position: token.position(),
+            }),
+        }
+    }
+
+    /// Plain substring search for `text`, used while the typed query is invalid
+    pub fn literal(text: &str, options: SearchOptions) -> Query {
+        Query::Term(options.literal(text))
+    }
+
+    pub fn matches(&self, process: &ProcessInfo, context: QueryContext) -> bool {
+        match self {
+            Query::Term(condition) => condition.matches(process, context),
+            Query::Not(inner) => !inner.matches(process, context),
+            Query::And(parts) => parts.iter().all(|part| part.matches(process, context)),
+            Query::Or(parts) => parts.iter().any(|part| part.matches(process, context)),
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, process: &ProcessInfo, context: QueryContext) -> bool {
+        match self {
+            Condition::Search {
+                pattern,
+                full_command,
+            } => {
+                pattern.is_match(&process.name.to_string_lossy())
+                    || (*full_command
+                        && (pattern.is_match(&process.command_line)
+                            || process
+                                .exe
+                                .as_ref()
+                                .is_some_and(|exe| pattern.is_match(&exe.to_string_lossy()))))
+            }
+            Condition::Numeric(field, comparison, value) => field
+                .value(process, context)
+                .is_some_and(|actual| comparison.holds(actual, *value)),
+            Condition::Text {
+                field,
+                text_match,
+                case_sensitive,
+            } => text_match.matches(&field.value(process), *case_sensitive),
+        }
+    }
+}
+
+impl NumericField {
+    fn value(self, process: &ProcessInfo, context: QueryContext) -> Option<f64> {
+        match self {
+            NumericField::Cpu => {
+                Some(f64::from(process.cpu_usage) / context.cpu_cores.max(1) as f64)
+            }
+            NumericField::Memory => Some(process.memory_usage as f64),
+            NumericField::MemoryPercent => {
+                Some(process.memory_usage as f64 / context.total_memory.max(1) as f64 * 100.0)
+            }
+            NumericField::Pid => Some(f64::from(process.pid.as_u32())),
+            NumericField::ParentPid => process.parent_pid.map(|pid| f64::from(pid.as_u32())),
+            NumericField::Threads => Some(process.thread_count as f64),
+        }
+    }
+}
+
+impl TextField {
+    fn value(self, process: &ProcessInfo) -> String {
+        match self {
+            TextField::Name => process.name.to_string_lossy().into_owned(),
+            TextField::Command => process.command_line.clone(),
+            TextField::User => process.user_label(),
+            TextField::Status => process.status.to_string(),
+        }
+    }
+}
+
+impl Comparison {
+    fn holds(self, actual: f64, expected: f64) -> bool {
+        match self {
+            Comparison::Less => actual < expected,
+            Comparison::LessOrEqual => actual <= expected,
+            Comparison::Equal => actual == expected,
+            Comparison::NotEqual => actual != expected,
+            Comparison::GreaterOrEqual => actual >= expected,
+            Comparison::Greater => actual > expected,
+        }
+    }
+}
+
+impl TextMatch {
+    fn matches(&self, value: &str, case_sensitive: bool) -> bool {
+        let folded = || {
+            if case_sensitive {
+                value.to_string()
+            } else {
+                value.to_lowercase()
+            }
+        };
+        match self {
+            TextMatch::Contains(needle) => folded().contains(needle.as_str()),
+            TextMatch::Equals(expected) => folded() == *expected,
+            TextMatch::NotEquals(expected) => folded() != *expected,
+            // Case is set on the regex itself when it is built
+            TextMatch::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String, usize),
+    Open(usize),
+    Close(usize),
+}
+
+impl Token {
+    fn position(&self) -> usize {
+        match self {
+            Token::Word(_, position) | Token::Open(position) | Token::Close(position) => *position,
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Token::Word(word, _) => word,
+            Token::Open(_) => "(",
+            Token::Close(_) => ")",
+        }
+    }
+
+    /// The boolean operator this token spells, if any
+    fn keyword(&self) -> Option<&'static str> {
+        match self {
+            Token::Word(word, _) if word.eq_ignore_ascii_case("and") => Some("AND"),
+            Token::Word(word, _) if word.eq_ignore_ascii_case("or") => Some("OR"),
+            Token::Word(word, _) if word.eq_ignore_ascii_case("not") => Some("NOT"),
+            _ => None,
+        }
+    }
+}
+
+/// Split on whitespace and parentheses; double quotes keep spaces inside a word
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open(position));
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close(position));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(quote_start, c)) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    chars.next();
+                    if c != '"' {
+                        word.push(c);
+                        continue;
+                    }
+                    // Copy everything up to the closing quote verbatim
+                    loop {
+                        match chars.next() {
+                            Some((_, '"')) => break,
+                            Some((_, c)) => word.push(c),
+                            None => {
+                                return Err(ParseError {
+                                    message: String::from("unclosed quote"),
+                                    position: quote_start,
+                                });
+                            }
+                        }
+                    }
+                }
+                tokens.push(Token::Word(word, position));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    /// Offset reported for errors at the end of the input
+    end: usize,
+    options: SearchOptions,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Query, ParseError> {
+        let mut parts = vec![self.parse_and()?];
+        while self.peek().and_then(Token::keyword) == Some("OR") {
+            self.next();
+            parts.push(self.parse_and()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Query::Or(parts)
+        })
+    }
+
+    /// Terms next to each other are joined with an implicit AND
+    fn parse_and(&mut self) -> Result<Query, ParseError> {
+        let mut parts = vec![self.parse_not()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Close(_)) => break,
+                Some(token) if token.keyword() == Some("OR") => break,
+                Some(token) if token.keyword() == Some("AND") => {
+                    self.next();
+                }
+                Some(_) => {}
+            }
+            parts.push(self.parse_not()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Query::And(parts)
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Query, ParseError> {
+        if self.peek().and_then(Token::keyword) == Some("NOT") {
+            self.next();
+            return Ok(Query::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Query, ParseError> {
+        match self.next() {
+            None => Err(ParseError {
+                message: String::from("expected a filter term"),
+                position: self.end,
+            }),
+            Some(Token::Open(position)) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close(_)) => Ok(inner),
+                    _ => Err(ParseError {
+                        message: String::from("unclosed '('"),
+                        position,
+                    }),
+                }
+            }
+            Some(Token::Close(position)) => Err(ParseError {
+                message: String::from("unexpected ')'"),
+                position,
+            }),
+            Some(token @ Token::Word(..)) if token.keyword().is_some() => Err(ParseError {
+                message: format!("expected a filter term before '{}'", token.text()),
+                position: token.position(),
+            }),
+            Some(Token::Word(word, position)) => {
+                parse_term(&word, position, self.options).map(Query::Term)
+            }
+        }
+    }
+}
+
+const OPERATORS: [(&str, Operator); 8] = [
+    (">=", Operator::Compare(Comparison::GreaterOrEqual)),
+    ("<=", Operator::Compare(Comparison::LessOrEqual)),
+    ("!=", Operator::Compare(Comparison::NotEqual)),
+    (">", Operator::Compare(Comparison::Greater)),
+    ("<", Operator::Compare(Comparison::Less)),
+    ("=", Operator::Compare(Comparison::Equal)),
+    (":", Operator::Colon),
+    ("~", Operator::Regex),
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Compare(Comparison),
+    Colon,
+    Regex,
+}
+
+fn parse_term(
+    word: &str,
+    position: usize,
+    options: SearchOptions,
+) -> Result<Condition, ParseError> {
+    // The operator starts at the first operator character in the word
+    let Some(op_start) = word.find(['>', '<', '=', '!', ':', '~']) else {
+        return Ok(options.literal(word));
+    };
+    let (field, rest) = word.split_at(op_start);
+    let (symbol, operator) = OPERATORS
+        .iter()
+        .find(|(symbol, _)| rest.starts_with(symbol))
+        .copied()
+        .ok_or_else(|| ParseError {
+            message: format!("unknown operator in '{}'", word),
+            position: position + field.chars().count(),
+        })?;
+    let value = &rest[symbol.len()..];
+    // Positions count characters, the same as the tokenizer
+    let op_position = position + field.chars().count();
+    let value_position = op_position + symbol.len();
+    let error = |message: String| ParseError {
+        message,
+        position: value_position,
+    };
+
+    if field.is_empty() {
+        return Err(ParseError {
+            message: format!("missing field name before '{}'", symbol),
+            position,
+        });
+    }
+    if value.is_empty() {
+        return Err(error(format!("missing value after '{}{}'", field, symbol)));
+    }
+
+    let field_name = field.to_lowercase();
+    let text_field = match field_name.as_str() {
+        "name" => Some(TextField::Name),
+        "cmd" | "command" => Some(TextField::Command),
+        "user" => Some(TextField::User),
+        "status" => Some(TextField::Status),
+        _ => None,
+    };
+    if let Some(text_field) = text_field {
+        let case_sensitive = options.case_sensitive;
+        let needle = if case_sensitive {
+            value.to_string()
+        } else {
+            value.to_lowercase()
+        };
+        let text_match = match operator {
+            Operator::Regex => RegexBuilder::new(value)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map(TextMatch::Regex)
+                .map_err(|e| error(format!("invalid regular expression: {}", e)))?,
+            // Name and command line are long, so ':' looks for a substring there
+            Operator::Colon if matches!(text_field, TextField::Name | TextField::Command) => {
+                TextMatch::Contains(needle)
+            }
+            Operator::Colon | Operator::Compare(Comparison::Equal) => TextMatch::Equals(needle),
+            Operator::Compare(Comparison::NotEqual) => TextMatch::NotEquals(needle),
+            Operator::Compare(_) => {
+                return Err(error(format!(
+                    "'{}' is text and cannot be compared with '{}'",
+                    field, symbol
+                )));
+            }
+        };
+        return Ok(Condition::Text {
+            field: text_field,
+            text_match,
+            case_sensitive,
+        });
+    }
+
+    let comparison = match operator {
+        Operator::Compare(comparison) => comparison,
+        Operator::Colon => Comparison::Equal,
+        Operator::Regex => {
+            return Err(error(format!("'{}' is numeric and cannot use '~'", field)));
+        }
+    };
+    let (numeric_field, amount) = match field_name.as_str() {
+        "cpu" => (
+            NumericField::Cpu,
+            parse_number(value.trim_end_matches('%'))
+                .ok_or_else(|| error(format!("expected a CPU percentage, found '{}'", value)))?,
+        ),
+        "mem" | "memory" => match value.strip_suffix('%') {
+            Some(percent) => (
+                NumericField::MemoryPercent,
+                parse_number(percent).ok_or_else(|| {
+                    error(format!("expected a memory percentage, found '{}'", value))
+                })?,
+            ),
+            None => (
+                NumericField::Memory,
+                parse_size(value).ok_or_else(|| {
+                    error(format!(
+                        "expected a memory size such as 500MB, found '{}'",
+                        value
+                    ))
+                })?,
+            ),
+        },
+        "pid" | "ppid" | "threads" => {
+            let number = value
+                .parse::<u64>()
+                .map_err(|_| error(format!("expected a whole number, found '{}'", value)))?;
+            let numeric_field = match field_name.as_str() {
+                "pid" => NumericField::Pid,
+                "ppid" => NumericField::ParentPid,
+                _ => NumericField::Threads,
+            };
+            (numeric_field, number as f64)
+        }
+        _ => {
+            return Err(ParseError {
+                message: format!(
+                    "unknown field '{}' (try cpu, mem, pid, ppid, threads, user, name, cmd or status)",
+                    field
+                ),
+                position,
+            });
+        }
+    };
+    Ok(Condition::Numeric(numeric_field, comparison, amount))
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Parse a size such as `512`, `500MB`, `1.5G` or `64KiB` into bytes (1 KB = 1024 bytes)
+fn parse_size(value: &str) -> Option<f64> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier: f64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    parse_number(number).map(|number| number * multiplier)
+}
@@ -0,0 +1,86 @@
+use crate::history::RingBuffer;
+use std::collections::{HashMap, HashSet};
+
+/// Critical temperature assumed for sensors that do not report one, in °C
+pub const DEFAULT_CRITICAL: f32 = 100.0;
+
+/// How close to the critical temperature a sensor counts as running hot, in °C
+pub const WARNING_MARGIN: f32 = 10.0;
+
+/// One hardware temperature sensor, all readings in °C
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorInfo {
+    pub label: String,
+    pub temperature: Option<f32>,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+/// How close a sensor is to its critical temperature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorLevel {
+    Normal,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+impl SensorInfo {
+    pub fn level(&self) -> SensorLevel {
+        let Some(temperature) = self.temperature else {
+            return SensorLevel::Unknown;
+        };
+        let critical = self.critical.unwrap_or(DEFAULT_CRITICAL);
+        if temperature >= critical {
+            SensorLevel::Critical
+        } else if temperature >= critical - WARNING_MARGIN {
+            SensorLevel::Warning
+        } else {
+            SensorLevel::Normal
+        }
+    }
+}
+
+/// Latest sensor readings plus per-sensor temperature history
+pub struct SensorManager {
+    sensors: Vec<SensorInfo>,
+    capacity: usize,
+    history: HashMap<String, RingBuffer<f32>>,
+}
+
+impl SensorManager {
+    pub fn new(capacity: usize) -> Self {
+        SensorManager {
+            sensors: Vec::new(),
+            capacity,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Replace the readings and record the temperature of every sensor that reported one
+    pub fn update(&mut self, mut sensors: Vec<SensorInfo>) {
+        sensors.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let present: HashSet<&str> = sensors.iter().map(|s| s.label.as_str()).collect();
+        self.history
+            .retain(|label, _| present.contains(label.as_str()));
+        for sensor in &sensors {
+            if let Some(temperature) = sensor.temperature {
+                self.history
+                    .entry(sensor.label.clone())
+                    .or_insert_with(|| RingBuffer::new(self.capacity))
+                    .push(temperature);
+            }
+        }
+        self.sensors = sensors;
+    }
+
+    /// Sensors ordered by label; empty on machines without sensors, such as most VMs
+    pub fn sensors(&self) -> &[SensorInfo] {
+        &self.sensors
+    }
+
+    pub fn history(&self, label: &str) -> Option<&RingBuffer<f32>> {
+        self.history.get(label)
+    }
+}
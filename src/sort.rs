@@ -0,0 +1,145 @@
+use crate::columns::ProcessColumn;
+use crate::process::ProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Anything the process list can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortField {
+    Name,
+    Column(ProcessColumn),
+}
+
+impl SortField {
+    pub fn label(self) -> &'static str {
+        match self {
+            SortField::Name => "Process Name",
+            SortField::Column(column) => column.label(),
+        }
+    }
+
+    fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            SortField::Name => a.name.cmp(&b.name),
+            SortField::Column(column) => column.compare(a, b),
+        }
+    }
+
+    /// Parse a short field name as used on the command line, e.g. `cpu` or `mem`
+    pub fn from_name(name: &str) -> Option<SortField> {
+        let column = match name.to_ascii_lowercase().as_str() {
+            "name" => return Some(SortField::Name),
+            "pid" => ProcessColumn::Pid,
+            "ppid" => ProcessColumn::ParentPid,
+            "user" => ProcessColumn::User,
+            "status" => ProcessColumn::Status,
+            "cpu" => ProcessColumn::Cpu,
+            "mem" | "memory" => ProcessColumn::Memory,
+            "vmem" => ProcessColumn::VirtualMemory,
+            "read" => ProcessColumn::DiskRead,
+            "write" => ProcessColumn::DiskWrite,
+            "runtime" => ProcessColumn::Runtime,
+            "threads" => ProcessColumn::Threads,
+            "cmd" | "command" => ProcessColumn::CommandLine,
+            _ => return None,
+        };
+        Some(SortField::Column(column))
+    }
+
+    /// Direction used when none is given: biggest first for numbers, A-Z for text
+    fn natural_descending(self) -> bool {
+        !matches!(
+            self,
+            SortField::Name
+                | SortField::Column(
+                    ProcessColumn::User | ProcessColumn::Status | ProcessColumn::CommandLine
+                )
+        )
+    }
+}
+
+/// One level of the sort order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn ascending(field: SortField) -> Self {
+        SortKey {
+            field,
+            descending: false,
+        }
+    }
+
+    pub fn descending(field: SortField) -> Self {
+        SortKey {
+            field,
+            descending: true,
+        }
+    }
+}
+
+/// Parse a comma-separated sort spec such as `cpu,name` or `mem:asc,pid:desc`
+pub fn parse_keys(spec: &str) -> Result<Vec<SortKey>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (name, direction) = part.split_once(':').unwrap_or((part, ""));
+            let field = SortField::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown sort field '{}' (try name, pid, ppid, user, status, cpu, mem, vmem, read, write, runtime, threads or cmd)",
+                    name
+                )
+            })?;
+            let descending = match direction.to_ascii_lowercase().as_str() {
+                "" => field.natural_descending(),
+                "asc" => false,
+                "desc" => true,
+                other => return Err(format!("unknown sort direction '{}' (use asc or desc)", other)),
+            };
+            Ok(SortKey { field, descending })
+        })
+        .collect()
+}
+
+/// Sort shown until the user picks their own
+pub fn default_sort() -> Vec<SortKey> {
+    vec![SortKey::ascending(SortField::Name)]
+}
+
+/// Compare by each key in turn, then by PID so equal rows keep a fixed order
+pub fn compare(keys: &[SortKey], a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+    keys.iter()
+        .map(|key| {
+            let ordering = key.field.compare(a, b);
+            if key.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.pid.cmp(&b.pid))
+}
+
+/// Update `keys` for a click on the `field` header.
+///
+/// A plain click sorts by `field` alone, flipping the direction if it was
+/// already the primary key. With `add_secondary` (shift-click) the field is
+/// appended as a further key, or flipped in place if it is already one.
+pub fn click(keys: &mut Vec<SortKey>, field: SortField, add_secondary: bool) {
+    if add_secondary {
+        match keys.iter_mut().find(|key| key.field == field) {
+            Some(key) => key.descending = !key.descending,
+            None => keys.push(SortKey::ascending(field)),
+        }
+    } else {
+        let descending = keys
+            .first()
+            .is_some_and(|key| key.field == field && !key.descending);
+        *keys = vec![SortKey { field, descending }];
+    }
+}
@@ -0,0 +1,676 @@
+use crate::disks::DiskInfo;
+use crate::history::LoadAverage;
+use crate::network::NetworkInfo;
+use crate::process::ProcessInfo;
+use crate::sensors::SensorInfo;
+use crate::system::{KillOutcome, KillSignal};
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::sync::{Arc, Mutex};
+use sysinfo::{Components, Disks, Networks, Pid, System, Uid, Users};
+
+/// Anything that can supply process and system data to the `SystemManager`.
+///
+/// The default implementation is `SysinfoSource`, which reads the live machine.
+/// `ScriptedSource` replays fixed snapshots so state logic can be tested deterministically.
+pub trait SystemSource {
+    fn refresh(&mut self);
+    fn processes(&self) -> Vec<ProcessInfo>;
+    fn kill_process(&mut self, pid: Pid, signal: KillSignal) -> KillOutcome;
+    fn renice(&mut self, pid: Pid, nice: i32) -> io::Result<()>;
+
+    fn os_name(&self) -> String;
+    fn os_version(&self) -> String;
+    fn kernel_version(&self) -> String;
+    fn hostname(&self) -> String;
+    fn cpu_brand(&self) -> String;
+    fn cpu_cores(&self) -> usize;
+    fn total_memory(&self) -> u64;
+    fn used_memory(&self) -> u64;
+    fn available_memory(&self) -> u64;
+    fn total_swap(&self) -> u64;
+    fn used_swap(&self) -> u64;
+    /// Overall CPU usage in percent
+    fn global_cpu_usage(&self) -> f32;
+    /// Usage in percent of each logical core
+    fn cpu_usages(&self) -> Vec<f32>;
+    /// Current frequency in MHz of each logical core
+    fn cpu_frequencies(&self) -> Vec<u64>;
+    fn load_average(&self) -> LoadAverage;
+    /// Seconds since boot
+    fn uptime(&self) -> u64;
+    /// Boot time in seconds since the Unix epoch
+    fn boot_time(&self) -> u64;
+    fn process_count(&self) -> usize;
+    /// Threads across all processes
+    fn thread_count(&self) -> usize;
+    /// Mounted filesystems with their I/O since the previous refresh
+    fn disks(&self) -> Vec<DiskInfo>;
+    /// Network interfaces with their traffic since the previous refresh
+    fn networks(&self) -> Vec<NetworkInfo>;
+    /// Temperature sensors; empty when the machine exposes none
+    fn sensors(&self) -> Vec<SensorInfo>;
+}
+
+/// Live data source backed by `sysinfo::System`
+pub struct SysinfoSource {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+    components: Components,
+    /// Read once at startup; accounts created later show up by UID
+    users: Users,
+}
+
+impl Default for SysinfoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SysinfoSource {
+    pub fn new() -> Self {
+        let mut system = System::new_all();
+
+        //Initial Refresh
+        system.refresh_all();
+
+        // sleep for MINIMUM_CPU_UPDATE_INTERVAL
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+        //second refresh for CPU usage
+        system.refresh_all();
+
+        SysinfoSource {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+        }
+    }
+}
+
+impl SystemSource for SysinfoSource {
+    fn refresh(&mut self) {
+        self.system.refresh_all();
+        // `true` drops filesystems that have been unmounted since the last refresh
+        self.disks.refresh(true);
+        self.networks.refresh(true);
+        self.components.refresh(true);
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.system
+            .processes()
+            .iter()
+            .map(|(pid, process)| {
+                let base = ProcessInfo::new(
+                    process.name().to_os_string(),
+                    process.parent(),
+                    *pid,
+                    process.cpu_usage(),
+                    process.memory(),
+                    process.run_time(),
+                    process.status(),
+                    process.accumulated_cpu_time(),
+                    process.disk_usage(),
+                );
+                let args: Vec<String> = process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect();
+                ProcessInfo {
+                    command_line: args.join(" "),
+                    args,
+                    exe: process.exe().map(|path| path.to_path_buf()),
+                    cwd: process.cwd().map(|path| path.to_path_buf()),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| self.users.get_user_by_id(uid))
+                        .map(|user| user.name().to_string()),
+                    uid: process.user_id().and_then(numeric_uid),
+                    gid: process.group_id().map(|gid| *gid),
+                    virtual_memory: process.virtual_memory(),
+                    start_time: process.start_time(),
+                    thread_count: process.tasks().map_or(1, |tasks| tasks.len().max(1)),
+                    is_thread: process.thread_kind().is_some(),
+                    ..base
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    fn kill_process(&mut self, pid: Pid, signal: KillSignal) -> KillOutcome {
+        let Some(target) = single_pid(pid) else {
+            return KillOutcome::Failed(format!("refusing to signal PID {}", pid.as_u32()));
+        };
+        if self.system.process(pid).is_none() {
+            return KillOutcome::NoSuchProcess;
+        }
+        // Call kill(2) directly so errno is read straight after the failed call.
+        // SAFETY: kill takes plain integers and touches no memory of ours; `target`
+        // is positive, so it names one process rather than a process group.
+        if unsafe { libc::kill(target, signal.number()) } == 0 {
+            KillOutcome::Sent
+        } else {
+            outcome_from_os_error(io::Error::last_os_error())
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn kill_process(&mut self, pid: Pid, signal: KillSignal) -> KillOutcome {
+        let Some(process) = self.system.process(pid) else {
+            return KillOutcome::NoSuchProcess;
+        };
+        match process.kill_with(signal.into()) {
+            None => KillOutcome::Unsupported,
+            Some(true) => KillOutcome::Sent,
+            Some(false) => KillOutcome::Failed(format!("could not send {}", signal)),
+        }
+    }
+
+    #[cfg(unix)]
+    fn renice(&mut self, pid: Pid, nice: i32) -> io::Result<()> {
+        let Some(who) = single_pid(pid) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("refusing to renice PID {}", pid.as_u32()),
+            ));
+        };
+        // SAFETY: setpriority takes plain integers and touches no memory of ours;
+        // `who` is positive, so it names that process rather than the caller.
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, who as libc::id_t, nice) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn renice(&mut self, _pid: Pid, _nice: i32) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "renice is not supported on this platform",
+        ))
+    }
+
+    fn os_name(&self) -> String {
+        System::name().unwrap_or_else(|| String::from("N/A"))
+    }
+
+    fn os_version(&self) -> String {
+        System::os_version().unwrap_or_else(|| String::from("N/A"))
+    }
+
+    fn kernel_version(&self) -> String {
+        System::kernel_version().unwrap_or_else(|| String::from("N/A"))
+    }
+
+    fn hostname(&self) -> String {
+        System::host_name().unwrap_or_else(|| String::from("N/A"))
+    }
+
+    fn cpu_brand(&self) -> String {
+        self.system
+            .cpus()
+            .first()
+            .map_or("N/A".to_string(), |cpu| cpu.brand().to_string())
+    }
+
+    fn cpu_cores(&self) -> usize {
+        self.system.cpus().len()
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.system.total_memory()
+    }
+
+    fn used_memory(&self) -> u64 {
+        self.system.used_memory()
+    }
+
+    fn available_memory(&self) -> u64 {
+        self.system.available_memory()
+    }
+
+    fn total_swap(&self) -> u64 {
+        self.system.total_swap()
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.system.used_swap()
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        self.system.global_cpu_usage()
+    }
+
+    fn cpu_usages(&self) -> Vec<f32> {
+        self.system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage())
+            .collect()
+    }
+
+    fn cpu_frequencies(&self) -> Vec<u64> {
+        self.system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.frequency())
+            .collect()
+    }
+
+    fn load_average(&self) -> LoadAverage {
+        let load = System::load_average();
+        LoadAverage {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        }
+    }
+
+    fn uptime(&self) -> u64 {
+        System::uptime()
+    }
+
+    fn boot_time(&self) -> u64 {
+        System::boot_time()
+    }
+
+    fn process_count(&self) -> usize {
+        // On Linux sysinfo also lists threads as processes; leave those out here
+        self.system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .count()
+    }
+
+    fn thread_count(&self) -> usize {
+        self.system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| process.tasks().map_or(1, |tasks| tasks.len().max(1)))
+            .sum()
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        self.disks
+            .list()
+            .iter()
+            .map(|disk| {
+                let usage = disk.usage();
+                DiskInfo {
+                    name: disk.name().to_string_lossy().into_owned(),
+                    mount_point: disk.mount_point().to_path_buf(),
+                    file_system: disk.file_system().to_string_lossy().into_owned(),
+                    total_space: disk.total_space(),
+                    available_space: disk.available_space(),
+                    is_removable: disk.is_removable(),
+                    read_bytes: usage.read_bytes,
+                    written_bytes: usage.written_bytes,
+                }
+            })
+            .collect()
+    }
+
+    fn networks(&self) -> Vec<NetworkInfo> {
+        self.networks
+            .list()
+            .iter()
+            .map(|(name, data)| NetworkInfo {
+                name: name.clone(),
+                mac_address: data.mac_address().to_string(),
+                ip_addresses: data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| network.to_string())
+                    .collect(),
+                received: data.received(),
+                transmitted: data.transmitted(),
+                total_received: data.total_received(),
+                total_transmitted: data.total_transmitted(),
+                total_packets_received: data.total_packets_received(),
+                total_packets_transmitted: data.total_packets_transmitted(),
+                total_errors_received: data.total_errors_on_received(),
+                total_errors_transmitted: data.total_errors_on_transmitted(),
+            })
+            .collect()
+    }
+
+    fn sensors(&self) -> Vec<SensorInfo> {
+        self.components
+            .list()
+            .iter()
+            .map(|component| SensorInfo {
+                label: component.label().to_string(),
+                temperature: component.temperature(),
+                max: component.max(),
+                critical: component.critical(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(unix)]
+fn numeric_uid(uid: &Uid) -> Option<u32> {
+    Some(**uid)
+}
+
+/// Windows user IDs are SIDs, which have no numeric form
+#[cfg(not(unix))]
+fn numeric_uid(_uid: &Uid) -> Option<u32> {
+    None
+}
+
+/// `pid` as a kernel PID naming exactly one process. PID 0 (which sysinfo lists
+/// on macOS) would address the caller or its process group instead, and values
+/// above `i32::MAX` would wrap to a negative process group ID.
+#[cfg(unix)]
+fn single_pid(pid: Pid) -> Option<libc::pid_t> {
+    libc::pid_t::try_from(pid.as_u32())
+        .ok()
+        .filter(|&pid| pid > 0)
+}
+
+#[cfg(unix)]
+fn outcome_from_os_error(error: io::Error) -> KillOutcome {
+    if error.kind() == io::ErrorKind::PermissionDenied {
+        return KillOutcome::PermissionDenied;
+    }
+    if error.raw_os_error() == Some(libc::ESRCH) {
+        return KillOutcome::NoSuchProcess;
+    }
+    KillOutcome::Failed(error.to_string())
+}
+
+/// In-memory data source that replays scripted process snapshots.
+///
+/// Each call to `refresh` advances to the next queued frame; once the script is
+/// exhausted the last frame stays current. Every signal is recorded in the shared
+/// kill log; terminating signals also remove the PID from the current frame.
+pub struct ScriptedSource {
+    current: Vec<ProcessInfo>,
+    frames: VecDeque<Vec<ProcessInfo>>,
+    cpu_cores: usize,
+    total_memory: u64,
+    used_memory: u64,
+    total_swap: u64,
+    used_swap: u64,
+    cpu_usages: Vec<f32>,
+    cpu_frequencies: Vec<u64>,
+    load_average: LoadAverage,
+    uptime: u64,
+    boot_time: u64,
+    thread_count: Option<usize>,
+    disks: Vec<DiskInfo>,
+    networks: Vec<NetworkInfo>,
+    sensors: Vec<SensorInfo>,
+    protected: HashSet<Pid>,
+    ignored: HashSet<(Pid, KillSignal)>,
+    kill_log: Arc<Mutex<Vec<(Pid, KillSignal)>>>,
+    renice_log: Arc<Mutex<Vec<(Pid, i32)>>>,
+}
+
+impl ScriptedSource {
+    pub fn new(processes: Vec<ProcessInfo>) -> Self {
+        ScriptedSource {
+            current: processes,
+            frames: VecDeque::new(),
+            cpu_cores: 4,
+            total_memory: 16 * 1024 * 1024 * 1024,
+            used_memory: 8 * 1024 * 1024 * 1024,
+            total_swap: 0,
+            used_swap: 0,
+            cpu_usages: vec![0.0; 4],
+            cpu_frequencies: vec![0; 4],
+            load_average: LoadAverage::default(),
+            uptime: 0,
+            boot_time: 0,
+            thread_count: None,
+            disks: Vec::new(),
+            networks: Vec::new(),
+            sensors: Vec::new(),
+            protected: HashSet::new(),
+            ignored: HashSet::new(),
+            kill_log: Arc::new(Mutex::new(Vec::new())),
+            renice_log: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn with_cpu_cores(mut self, cpu_cores: usize) -> Self {
+        self.cpu_cores = cpu_cores;
+        self.cpu_usages.resize(cpu_cores, 0.0);
+        self.cpu_frequencies.resize(cpu_cores, 0);
+        self
+    }
+
+    /// Per-core usage in percent; also sets the core count
+    pub fn with_cpu_usages(mut self, cpu_usages: Vec<f32>) -> Self {
+        self.cpu_cores = cpu_usages.len();
+        self.cpu_frequencies.resize(cpu_usages.len(), 0);
+        self.cpu_usages = cpu_usages;
+        self
+    }
+
+    /// Per-core frequency in MHz; also sets the core count
+    pub fn with_cpu_frequencies(mut self, cpu_frequencies: Vec<u64>) -> Self {
+        self.cpu_cores = cpu_frequencies.len();
+        self.cpu_usages.resize(cpu_frequencies.len(), 0.0);
+        self.cpu_frequencies = cpu_frequencies;
+        self
+    }
+
+    pub fn with_swap(mut self, total_swap: u64, used_swap: u64) -> Self {
+        self.total_swap = total_swap;
+        self.used_swap = used_swap;
+        self
+    }
+
+    pub fn with_total_memory(mut self, total_memory: u64) -> Self {
+        self.total_memory = total_memory;
+        self
+    }
+
+    pub fn with_used_memory(mut self, used_memory: u64) -> Self {
+        self.used_memory = used_memory;
+        self
+    }
+
+    pub fn with_load_average(mut self, one: f64, five: f64, fifteen: f64) -> Self {
+        self.load_average = LoadAverage { one, five, fifteen };
+        self
+    }
+
+    /// Boot time in seconds since the Unix epoch and seconds elapsed since then
+    pub fn with_boot_time(mut self, boot_time: u64, uptime: u64) -> Self {
+        self.boot_time = boot_time;
+        self.uptime = uptime;
+        self
+    }
+
+    /// Total thread count; defaults to one thread per process
+    pub fn with_thread_count(mut self, thread_count: usize) -> Self {
+        self.thread_count = Some(thread_count);
+        self
+    }
+
+    pub fn with_disks(mut self, disks: Vec<DiskInfo>) -> Self {
+        self.disks = disks;
+        self
+    }
+
+    pub fn with_networks(mut self, networks: Vec<NetworkInfo>) -> Self {
+        self.networks = networks;
+        self
+    }
+
+    pub fn with_sensors(mut self, sensors: Vec<SensorInfo>) -> Self {
+        self.sensors = sensors;
+        self
+    }
+
+    /// Queue a snapshot that becomes current on the next `refresh`
+    pub fn then(mut self, processes: Vec<ProcessInfo>) -> Self {
+        self.frames.push_back(processes);
+        self
+    }
+
+    /// Make signals and renices of `pid` fail with a permission error
+    pub fn protect(mut self, pid: Pid) -> Self {
+        self.protected.insert(pid);
+        self
+    }
+
+    /// Make `pid` survive `signal`; the signal is still reported as sent
+    pub fn ignore(mut self, pid: Pid, signal: KillSignal) -> Self {
+        self.ignored.insert((pid, signal));
+        self
+    }
+
+    /// Shared handle to every PID and signal this source was asked to send
+    pub fn kill_log(&self) -> Arc<Mutex<Vec<(Pid, KillSignal)>>> {
+        Arc::clone(&self.kill_log)
+    }
+
+    /// Shared handle to every successful renice as (PID, nice value)
+    pub fn renice_log(&self) -> Arc<Mutex<Vec<(Pid, i32)>>> {
+        Arc::clone(&self.renice_log)
+    }
+}
+
+impl SystemSource for ScriptedSource {
+    fn refresh(&mut self) {
+        if let Some(next) = self.frames.pop_front() {
+            self.current = next;
+        }
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.current.clone()
+    }
+
+    fn kill_process(&mut self, pid: Pid, signal: KillSignal) -> KillOutcome {
+        self.kill_log.lock().unwrap().push((pid, signal));
+        if !self.current.iter().any(|p| p.pid == pid) {
+            return KillOutcome::NoSuchProcess;
+        }
+        if self.protected.contains(&pid) {
+            return KillOutcome::PermissionDenied;
+        }
+        if !matches!(signal, KillSignal::Stop | KillSignal::Continue)
+            && !self.ignored.contains(&(pid, signal))
+        {
+            self.current.retain(|p| p.pid != pid);
+        }
+        KillOutcome::Sent
+    }
+
+    fn renice(&mut self, pid: Pid, nice: i32) -> io::Result<()> {
+        if !self.current.iter().any(|p| p.pid == pid) {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no such process"));
+        }
+        if self.protected.contains(&pid) {
+            return Err(io::Error::from(io::ErrorKind::PermissionDenied));
+        }
+        self.renice_log.lock().unwrap().push((pid, nice));
+        Ok(())
+    }
+
+    fn os_name(&self) -> String {
+        String::from("Scripted OS")
+    }
+
+    fn os_version(&self) -> String {
+        String::from("1.0")
+    }
+
+    fn kernel_version(&self) -> String {
+        String::from("scripted")
+    }
+
+    fn hostname(&self) -> String {
+        String::from("scripted-host")
+    }
+
+    fn cpu_brand(&self) -> String {
+        String::from("Scripted CPU")
+    }
+
+    fn cpu_cores(&self) -> usize {
+        self.cpu_cores
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    fn used_memory(&self) -> u64 {
+        self.used_memory
+    }
+
+    fn available_memory(&self) -> u64 {
+        self.total_memory.saturating_sub(self.used_memory)
+    }
+
+    fn total_swap(&self) -> u64 {
+        self.total_swap
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.used_swap
+    }
+
+    fn global_cpu_usage(&self) -> f32 {
+        if self.cpu_usages.is_empty() {
+            0.0
+        } else {
+            self.cpu_usages.iter().sum::<f32>() / self.cpu_usages.len() as f32
+        }
+    }
+
+    fn cpu_usages(&self) -> Vec<f32> {
+        self.cpu_usages.clone()
+    }
+
+    fn cpu_frequencies(&self) -> Vec<u64> {
+        self.cpu_frequencies.clone()
+    }
+
+    fn load_average(&self) -> LoadAverage {
+        self.load_average
+    }
+
+    fn uptime(&self) -> u64 {
+        self.uptime
+    }
+
+    fn boot_time(&self) -> u64 {
+        self.boot_time
+    }
+
+    fn process_count(&self) -> usize {
+        self.current.iter().filter(|p| !p.is_thread).count()
+    }
+
+    fn thread_count(&self) -> usize {
+        self.thread_count.unwrap_or(self.current.len())
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        self.disks.clone()
+    }
+
+    fn networks(&self) -> Vec<NetworkInfo> {
+        self.networks.clone()
+    }
+
+    fn sensors(&self) -> Vec<SensorInfo> {
+        self.sensors.clone()
+    }
+}
@@ -1,14 +1,37 @@
+use crate::alerts::{
+    self, AlertAction, AlertEngine, AlertEvent, AlertInputs, AlertKind, AlertRule,
+};
+use crate::columns::{self, ColumnLayout, ProcessColumn};
+use crate::disks::DiskManager;
+use crate::events::{EventFeed, LifecycleKind, ProcessEvent};
+use crate::format::{format_duration, format_megabytes, format_optional};
+use crate::history::{ProcessHistory, ProcessSample, RingBuffer};
 use crate::logger;
+use crate::network::NetworkManager;
+use crate::presets::{self, FilterPreset};
 use crate::process::ProcessInfo;
-use crate::system::SystemManager;
+use crate::query::{ParseError, Query, QueryContext, SearchOptions};
+use crate::sensors::SensorManager;
+use crate::sort::{self, SortField, SortKey};
+use crate::source::SystemSource;
+use crate::system::{self, KillOutcome, KillSignal, SystemManager};
+use crate::tree;
+use crate::watchdog::{WatchEntry, WatchEvent, WatchTarget, Watchdog};
 
 use iced::{Application, Command, Event, Subscription, Theme, event, keyboard, time};
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{fs, io};
-use sysinfo::Pid;
+use sysinfo::{Pid, ProcessStatus};
+
+/// How often process and system data is refreshed
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a toast notification stays up
+pub const TOAST_DURATION: Duration = Duration::from_secs(3);
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum AppTheme {
@@ -27,14 +50,31 @@ impl From<AppTheme> for Theme {
 
 /// Structure to hold and manage application settings for persistence
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 struct AppSettings {
     theme: AppTheme,
+    grace_period_secs: u64,
+    history_retention_secs: u64,
+    sort: Vec<SortKey>,
+    columns: Vec<ColumnLayout>,
+    search: SearchOptions,
+    presets: Vec<FilterPreset>,
+    alert_rules: Vec<AlertRule>,
+    watchdog: Vec<WatchEntry>,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         AppSettings {
             theme: AppTheme::Dark, // Default to Dark theme
+            grace_period_secs: 5,
+            history_retention_secs: 600,
+            sort: sort::default_sort(),
+            columns: columns::default_columns(),
+            search: SearchOptions::default(),
+            presets: presets::default_presets(),
+            alert_rules: alerts::default_rules(),
+            watchdog: Vec::new(),
         }
     }
 }
@@ -52,11 +92,11 @@ impl AppSettings {
                 Ok(contents) => match toml::from_str(&contents) {
                     Ok(settings) => return settings,
                     Err(e) => {
-                        eprintln!("Error parsing config file: {}", e);
+                        error!("Error parsing config file: {}", e);
                     }
                 },
                 Err(e) if e.kind() != io::ErrorKind::NotFound => {
-                    eprintln!("Error reading config file: {}", e);
+                    error!("Error reading config file: {}", e);
                 }
                 _ => {}
             }
@@ -68,40 +108,122 @@ impl AppSettings {
         if let Some(path) = Self::config_path() {
             let config_dir = path.parent().unwrap();
             if let Err(e) = fs::create_dir_all(config_dir) {
-                eprintln!("Error creating config directory: {}", e);
+                error!("Error creating config directory: {}", e);
                 return;
             }
             match toml::to_string(self) {
                 Ok(contents) => {
                     if let Err(e) = fs::write(path, contents) {
-                        eprintln!("Error saving config file: {}", e);
+                        error!("Error saving config file: {}", e);
                     }
                 }
                 Err(e) => {
-                    eprintln!("Error serializing config: {}", e);
+                    error!("Error serializing config: {}", e);
                 }
             }
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum SortBy {
-    AlphaAsc,
-    AlphaDesc,
-    CpuAsc,
-    CpuDesc,
-    MemAsc,
-    MemDesc,
+/// Time span shown by the system charts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartWindow {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl ChartWindow {
+    pub const ALL: [ChartWindow; 3] = [
+        ChartWindow::OneMinute,
+        ChartWindow::FiveMinutes,
+        ChartWindow::OneHour,
+    ];
+
+    /// Number of refresh samples covering the window
+    pub fn samples(self) -> usize {
+        let secs = match self {
+            ChartWindow::OneMinute => 60,
+            ChartWindow::FiveMinutes => 5 * 60,
+            ChartWindow::OneHour => 60 * 60,
+        };
+        (secs / REFRESH_INTERVAL.as_secs()) as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartWindow::OneMinute => "1 min",
+            ChartWindow::FiveMinutes => "5 min",
+            ChartWindow::OneHour => "1 h",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
     Processes,
     System,
+    Disks,
+    Network,
+    Alerts,
+    Events,
+    Watchdog,
     Settings,
 }
 
+impl Tab {
+    pub const ALL: [Tab; 8] = [
+        Tab::Processes,
+        Tab::System,
+        Tab::Disks,
+        Tab::Network,
+        Tab::Alerts,
+        Tab::Events,
+        Tab::Watchdog,
+        Tab::Settings,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tab::Processes => "Processes",
+            Tab::System => "System",
+            Tab::Disks => "Disks",
+            Tab::Network => "Network",
+            Tab::Alerts => "Alerts",
+            Tab::Events => "Events",
+            Tab::Watchdog => "Watchdog",
+            Tab::Settings => "Settings",
+        }
+    }
+}
+
+/// Which process a confirmed kill is aimed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillTarget {
+    Process,
+    Parent,
+    Tree,
+    Selection,
+}
+
+/// A SIGTERM that escalates to SIGKILL if the process outlives its deadline
+#[derive(Debug, Clone)]
+pub struct Termination {
+    pub pid: Pid,
+    /// Start time of the signalled process, so a reused PID is not mistaken for it
+    pub start_time: u64,
+    pub target: String,
+    pub deadline: Instant,
+}
+
+impl Termination {
+    /// Seconds left before escalation, rounded up
+    pub fn remaining_secs(&self, now: Instant) -> u64 {
+        let remaining = self.deadline.saturating_duration_since(now);
+        remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToastType {
     Success,
@@ -111,16 +233,54 @@ pub enum ToastType {
 #[derive(Debug, Clone)]
 pub enum Message {
     ProcessSelected(Pid),
+    SelectAllFiltered,
+    ClearSelection,
     SearchChanged(String),
-    SortAlpha,
-    SortCpu,
-    SortMem,
+    ToggleSearchRegex,
+    ToggleSearchCase,
+    ToggleSearchFullCommand,
+    PresetSelected(String),
+    PresetNameChanged(String),
+    SavePreset,
+    DeletePreset,
+    ToggleTreeMode,
+    ToggleExpanded(Pid),
+    /// Header click; secondary key if shift is held
+    SortClicked(SortField),
     RefreshTick(time::Instant),
     ToggleTheme,
     TabSelected(Tab),
+    ChartWindowSelected(ChartWindow),
     RequestKill,
+    RequestKillParent,
+    RequestKillTree,
+    SignalSelected(KillSignal),
     ConfirmKill,
+    ConfirmGracefulKill,
+    CancelEscalation(Pid),
     CancelKill,
+    GracePeriodChanged(u64),
+    ToggleColumn(ProcessColumn),
+    MoveColumn(ProcessColumn, isize),
+    ResizeColumn(ProcessColumn, f32),
+    ToggleAlertRule(usize),
+    ClearAlertHistory,
+    EventFilterChanged(String),
+    EventKindSelected(Option<LifecycleKind>),
+    ClearEvents,
+    /// Pin the selected process to the watchdog
+    WatchSelected,
+    WatchPatternChanged(String),
+    WatchCommandChanged(String),
+    ToggleWatchByCommandLine,
+    AddWatch,
+    ToggleWatch(usize),
+    ResetWatch(usize),
+    RemoveWatch(usize),
+    HistoryRetentionChanged(u64),
+    NiceValueChanged(i32),
+    ReniceSelected,
+    ExportSelection,
     ExportToCsv,
     ExportFinished(Result<String, String>),
     HideToast,
@@ -128,17 +288,84 @@ pub enum Message {
     LogsLoaded(Result<Vec<String>, String>),
     EventOccurred(iced::Event),
 }
+
+/// Follow-up work requested by TarnerMonitor::handle. The iced GUI turns it
+/// into a Command; the terminal UI runs it inline
+#[derive(Debug)]
+pub enum Effect {
+    None,
+    /// Send Message::HideToast once the delay has passed
+    HideToast(Duration),
+    /// Write the processes to CSV, then send Message::ExportFinished
+    Export {
+        processes: Vec<ProcessInfo>,
+        cpu_cores: usize,
+        total_memory: u64,
+    },
+    /// Read the log file, then send Message::LogsLoaded
+    LoadLogs,
+}
+
 pub struct TarnerMonitor {
     pub processes: Vec<ProcessInfo>,
     pub selected_process: Option<ProcessInfo>,
-    pub search_str: String,
+    pub selected_pids: BTreeSet<Pid>,
+    selection_anchor: Option<Pid>,
+    modifiers: keyboard::Modifiers,
+    search_str: String,
+    search_options: SearchOptions,
+    /// The search box parsed as a filter query, updated whenever the search changes
+    query: Result<Query, ParseError>,
+    /// What the list is filtered by: the parsed query, or a literal match while it
+    /// does not parse
+    filter: Query,
+    pub tree_mode: bool,
+    pub collapsed: HashSet<Pid>,
     pub system_manager: SystemManager,
-    pub current_sort: SortBy,
+    pub disk_manager: DiskManager,
+    pub network_manager: NetworkManager,
+    pub sensor_manager: SensorManager,
+    pub alert_engine: AlertEngine,
+    /// Processes that started or exited, found by diffing refreshes
+    pub event_feed: EventFeed,
+    event_filter: String,
+    /// The events filter parsed, updated whenever the filter changes
+    event_query: Query,
+    /// Only show events of this kind, or all when None
+    pub event_kind: Option<LifecycleKind>,
+    /// Pinned processes that are restarted when they exit
+    pub watchdog: Watchdog,
+    /// Name or command-line text typed for a new watchdog entry
+    pub watch_pattern: String,
+    pub watch_command: String,
+    pub watch_by_command_line: bool,
+    /// Sort keys, primary first; PID breaks any remaining ties
+    pub current_sort: Vec<SortKey>,
     pub theme: AppTheme,
     pub active_tab: Tab,
+    pub chart_window: ChartWindow,
     pub kill_confirm: bool,
+    pub kill_target: KillTarget,
+    /// PIDs and start times of a tree or bulk kill, captured when its
+    /// confirmation was opened
+    pub kill_targets: Vec<(Pid, u64)>,
+    pub kill_signal: KillSignal,
+    pub grace_period_secs: u64,
+    pub terminations: Vec<Termination>,
+    pub nice_value: i32,
+    pub history_retention_secs: u64,
+    /// Visible process table columns, in display order, after the name column
+    pub columns: Vec<ColumnLayout>,
+    /// Saved searches with their sort and columns, picked from the Processes tab
+    pub presets: Vec<FilterPreset>,
+    /// Preset last applied or saved, shown in the preset dropdown
+    pub active_preset: Option<String>,
+    /// Name typed for the next preset to save
+    pub preset_name: String,
+    process_history: ProcessHistory,
     pub toast: Option<(String, ToastType)>,
     pub log_lines: Vec<String>,
+    persist_settings: bool,
 }
 
 impl Default for TarnerMonitor {
@@ -149,8 +376,20 @@ impl Default for TarnerMonitor {
 
 impl TarnerMonitor {
     pub fn new() -> Self {
-        let settings = AppSettings::load();
-        let system_manager = SystemManager::new();
+        Self::build(SystemManager::new(), AppSettings::load(), true)
+    }
+
+    /// Create a monitor fed by the given data source instead of the live system.
+    /// Settings start from defaults and are never written to the config file.
+    pub fn with_source(source: impl SystemSource + 'static) -> Self {
+        Self::build(
+            SystemManager::with_source(Box::new(source)),
+            AppSettings::default(),
+            false,
+        )
+    }
+
+    fn build(system_manager: SystemManager, settings: AppSettings, persist_settings: bool) -> Self {
         let processes = system_manager.get_processes();
 
         info!("Application started");
@@ -158,40 +397,254 @@ impl TarnerMonitor {
         let mut app = TarnerMonitor {
             processes,
             selected_process: None,
+            selected_pids: BTreeSet::new(),
+            selection_anchor: None,
+            modifiers: keyboard::Modifiers::default(),
             search_str: String::new(),
+            search_options: settings.search,
+            query: Query::parse_with("", settings.search),
+            filter: Query::literal("", settings.search),
+            tree_mode: false,
+            collapsed: HashSet::new(),
+            disk_manager: DiskManager::new(SystemManager::HISTORY_CAPACITY),
+            network_manager: NetworkManager::new(SystemManager::HISTORY_CAPACITY),
+            sensor_manager: SensorManager::new(SystemManager::HISTORY_CAPACITY),
+            alert_engine: AlertEngine::new(settings.alert_rules),
+            event_feed: EventFeed::new(),
+            event_filter: String::new(),
+            event_query: EventFeed::parse_filter(""),
+            event_kind: None,
+            watchdog: Watchdog::new(settings.watchdog),
+            watch_pattern: String::new(),
+            watch_command: String::new(),
+            watch_by_command_line: false,
             system_manager,
-            current_sort: SortBy::AlphaAsc,
+            current_sort: settings.sort,
             theme: settings.theme,
             active_tab: Tab::Processes,
+            chart_window: ChartWindow::OneMinute,
             kill_confirm: false,
+            kill_target: KillTarget::Process,
+            kill_targets: Vec::new(),
+            kill_signal: KillSignal::Term,
+            grace_period_secs: settings.grace_period_secs,
+            terminations: Vec::new(),
+            nice_value: 10,
+            history_retention_secs: settings.history_retention_secs,
+            columns: settings.columns,
+            presets: settings.presets,
+            active_preset: None,
+            preset_name: String::new(),
+            process_history: ProcessHistory::new(history_samples(settings.history_retention_secs)),
             toast: None,
             log_lines: Vec::new(),
+            persist_settings,
         };
 
         app.apply_sort();
+        app.process_history.record(&app.processes);
+        app.disk_manager.update(app.system_manager.get_disks());
+        app.network_manager
+            .update(app.system_manager.get_networks());
+        app.sensor_manager.update(app.system_manager.get_sensors());
         app
     }
 
+    /// Recorded CPU, memory and disk samples for a process, oldest first
+    pub fn process_history(&self, pid: Pid) -> Option<&RingBuffer<ProcessSample>> {
+        self.process_history.get(pid)
+    }
+
+    pub fn search_str(&self) -> &str {
+        &self.search_str
+    }
+
+    pub fn search_options(&self) -> SearchOptions {
+        self.search_options
+    }
+
+    /// Replace the search box text and re-parse it
+    pub fn set_search(&mut self, search: String) {
+        self.search_str = search;
+        self.update_query();
+    }
+
+    /// The search box parsed as a filter query
+    pub fn query(&self) -> Result<&Query, &ParseError> {
+        self.query.as_ref()
+    }
+
+    // Parse the search once per change rather than on every render
+    fn update_query(&mut self) {
+        self.query = Query::parse_with(&self.search_str, self.search_options);
+        self.filter = match &self.query {
+            Ok(query) => query.clone(),
+            // While a query is half typed, fall back to a plain substring search
+            Err(_) => Query::literal(&self.search_str, self.search_options),
+        };
+    }
+
+    pub fn event_filter(&self) -> &str {
+        &self.event_filter
+    }
+
+    /// Lifecycle events matching the Events tab filters, newest first
+    pub fn filtered_events(&self) -> Vec<&ProcessEvent> {
+        let context = QueryContext {
+            cpu_cores: self.system_manager.cpu_cores,
+            total_memory: self.system_manager.total_memory,
+        };
+        self.event_feed
+            .filtered(&self.event_query, self.event_kind, context)
+    }
+
     // For searching processes
     pub fn get_filtered(&self) -> Vec<&ProcessInfo> {
+        let context = QueryContext {
+            cpu_cores: self.system_manager.cpu_cores,
+            total_memory: self.system_manager.total_memory,
+        };
         self.processes
             .iter()
-            .filter(|x| {
-                if self.search_str.is_empty() {
-                    true
-                } else {
-                    x.name
-                        .to_string_lossy()
-                        .to_lowercase()
-                        .contains(&self.search_str.to_lowercase())
-                }
-            })
+            .filter(|p| self.filter.matches(p, context))
             .collect()
     }
 
+    /// PIDs in the order they are currently listed, flat or tree
+    pub fn visible_pids(&self) -> Vec<Pid> {
+        if self.tree_mode {
+            self.tree_rows().iter().map(|r| r.process.pid).collect()
+        } else {
+            self.get_filtered().iter().map(|p| p.pid).collect()
+        }
+    }
+
+    /// Click handling: plain click selects one row, ctrl/cmd toggles a row,
+    /// shift extends from the last clicked row over the visible order
+    pub fn select_process(&mut self, pid: Pid, modifiers: keyboard::Modifiers) {
+        if modifiers.command() {
+            if self.selected_pids.remove(&pid) {
+                if self.selected_process.as_ref().map(|p| p.pid) == Some(pid) {
+                    let next = self.selected_pids.iter().next().copied();
+                    self.focus_process(next);
+                }
+            } else {
+                self.selected_pids.insert(pid);
+                self.focus_process(Some(pid));
+            }
+            self.selection_anchor = Some(pid);
+            return;
+        }
+
+        if modifiers.shift()
+            && let Some(anchor) = self.selection_anchor
+        {
+            let visible = self.visible_pids();
+            let from = visible.iter().position(|p| *p == anchor);
+            let to = visible.iter().position(|p| *p == pid);
+            if let (Some(from), Some(to)) = (from, to) {
+                self.selected_pids = visible[from.min(to)..=from.max(to)]
+                    .iter()
+                    .copied()
+                    .collect();
+                self.focus_process(Some(pid));
+                return;
+            }
+        }
+
+        self.selected_pids = BTreeSet::from([pid]);
+        self.selection_anchor = Some(pid);
+        self.focus_process(Some(pid));
+    }
+
+    fn focus_process(&mut self, pid: Option<Pid>) {
+        self.selected_process =
+            pid.and_then(|pid| self.processes.iter().find(|p| p.pid == pid).cloned());
+    }
+
+    // Single-process actions fall back to a lone selected row when nothing is focused
+    fn focus_single_selection(&mut self) {
+        if self.selected_process.is_none() && self.selected_pids.len() == 1 {
+            self.focus_process(self.selected_pids.first().copied());
+        }
+    }
+
+    /// Parent and tree kills act on one process, so they are off for a multi-selection
+    pub fn single_kill_enabled(&self) -> bool {
+        self.selected_pids.len() <= 1
+    }
+
+    /// PIDs bulk actions apply to: the multi-selection, or else the focused process
+    pub fn action_pids(&self) -> Vec<Pid> {
+        if self.selected_pids.is_empty() {
+            self.selected_process.iter().map(|p| p.pid).collect()
+        } else {
+            self.selected_pids.iter().copied().collect()
+        }
+    }
+
+    /// Set the nice value on every process bulk actions apply to
+    pub fn renice_selected(&mut self) -> (usize, usize) {
+        let pids = self.action_pids();
+        let mut reniced = 0;
+        for &pid in &pids {
+            match self.system_manager.renice_process(pid, self.nice_value) {
+                Ok(()) => reniced += 1,
+                Err(e) => error!(
+                    "Failed to renice {} to {}: {}",
+                    self.describe_pid(pid),
+                    self.nice_value,
+                    e
+                ),
+            }
+        }
+        (reniced, pids.len())
+    }
+
+    /// Rows of the hierarchical view; search matches keep their ancestors visible
+    pub fn tree_rows(&self) -> Vec<tree::TreeRow<'_>> {
+        let visible = tree::with_ancestors(&self.processes, &self.get_filtered());
+        tree::build_rows(&self.processes, &visible, &self.collapsed)
+    }
+
     pub fn refresh_processes(&mut self) {
         self.system_manager.refresh();
-        self.processes = self.system_manager.get_processes();
+        let previous = std::mem::replace(&mut self.processes, self.system_manager.get_processes());
+        for event in self.event_feed.record(&previous, &self.processes) {
+            let process = &event.process;
+            let name = process.name.to_string_lossy();
+            let pid = process.pid.as_u32();
+            match event.kind {
+                LifecycleKind::Started => info!(
+                    "Process started: {} (PID {}, parent {})",
+                    name,
+                    pid,
+                    process
+                        .parent_pid
+                        .map_or_else(|| "none".to_string(), |p| p.as_u32().to_string())
+                ),
+                LifecycleKind::Exited => info!(
+                    "Process exited: {} (PID {}) after {}, last seen at {:.1}% CPU and {}",
+                    name,
+                    pid,
+                    format_duration(process.run_time),
+                    process.cpu_usage,
+                    format_megabytes(process.memory_usage)
+                ),
+            }
+        }
+        self.process_history.record(&self.processes);
+        self.disk_manager.update(self.system_manager.get_disks());
+        self.network_manager
+            .update(self.system_manager.get_networks());
+        self.sensor_manager
+            .update(self.system_manager.get_sensors());
+
+        let processes = &self.processes;
+        self.collapsed
+            .retain(|pid| processes.iter().any(|p| p.pid == *pid));
+        self.selected_pids
+            .retain(|pid| processes.iter().any(|p| p.pid == *pid));
 
         if let Some(selected_proc) = &self.selected_process {
             let pid = selected_proc.pid;
@@ -201,54 +654,511 @@ impl TarnerMonitor {
 
     // Sorting Processes
     pub fn apply_sort(&mut self) {
-        match self.current_sort {
-            SortBy::AlphaAsc => {
-                self.processes.sort_by(|a, b| a.name.cmp(&b.name));
-            }
-            SortBy::AlphaDesc => {
-                self.processes.sort_by(|a, b| b.name.cmp(&a.name));
-            }
-            SortBy::CpuAsc => {
-                self.processes.sort_by(|a, b| {
-                    a.cpu_usage
-                        .partial_cmp(&b.cpu_usage)
-                        .unwrap_or(std::cmp::Ordering::Equal)
+        let keys = &self.current_sort;
+        self.processes.sort_by(|a, b| sort::compare(keys, a, b));
+    }
+
+    /// PIDs the pending kill is aimed at, in the order signals should be sent
+    pub fn kill_target_pids(&self) -> Vec<Pid> {
+        match (self.kill_target, self.selected_process.as_ref()) {
+            (KillTarget::Tree | KillTarget::Selection, _) => {
+                self.kill_targets.iter().map(|&(pid, _)| pid).collect()
+            }
+            (KillTarget::Process, Some(process)) => vec![process.pid],
+            (KillTarget::Parent, Some(process)) => process.parent_pid.into_iter().collect(),
+            (_, None) => Vec::new(),
+        }
+    }
+
+    // PIDs with the start time of the process currently holding them
+    fn snapshot_targets(&self, pids: impl IntoIterator<Item = Pid>) -> Vec<(Pid, u64)> {
+        pids.into_iter()
+            .filter_map(|pid| self.processes.iter().find(|p| p.pid == pid))
+            .map(|p| (p.pid, p.start_time))
+            .collect()
+    }
+
+    // Whether a captured tree or bulk target has since been replaced by a new
+    // process reusing its PID
+    fn target_replaced(&self, pid: Pid) -> bool {
+        matches!(self.kill_target, KillTarget::Tree | KillTarget::Selection)
+            && self
+                .kill_targets
+                .iter()
+                .find(|(target, _)| *target == pid)
+                .is_some_and(|&(_, start_time)| {
+                    self.processes
+                        .iter()
+                        .any(|p| p.pid == pid && p.start_time != start_time)
+                })
+    }
+
+    /// Processes the pending kill would affect, in the order signals are sent
+    pub fn kill_preview(&self) -> Vec<&ProcessInfo> {
+        self.kill_target_pids()
+            .into_iter()
+            .filter_map(|pid| self.processes.iter().find(|p| p.pid == pid))
+            .collect()
+    }
+
+    /// Title and description for the pending kill of `process`; tree and bulk
+    /// kills list their targets from kill_preview after the description
+    pub fn kill_prompt(&self, process: &ProcessInfo) -> (String, String) {
+        let name = process.name.to_string_lossy();
+        let signal = self.kill_signal;
+        match self.kill_target {
+            KillTarget::Process => (
+                format!("End process '{}'?", name),
+                format!(
+                    "This will send {} to the selected process (PID: {}).",
+                    signal,
+                    process.pid.as_u32()
+                ),
+            ),
+            KillTarget::Parent => (
+                format!("End parent process of '{}'?", name),
+                format!(
+                    "This will send {} to the parent (PID: {}) of the selected process (PID: {}).",
+                    signal,
+                    process
+                        .parent_pid
+                        .map_or_else(|| "N/A".to_string(), |pid| pid.as_u32().to_string()),
+                    process.pid.as_u32()
+                ),
+            ),
+            KillTarget::Tree => (
+                format!("End process tree of '{}'?", name),
+                format!(
+                    "This will send {} to these processes, children first:",
+                    signal
+                ),
+            ),
+            KillTarget::Selection => (
+                format!("End {} selected processes?", self.kill_target_pids().len()),
+                format!("This will send {} to these processes:", signal),
+            ),
+        }
+    }
+
+    fn describe_pid(&self, pid: Pid) -> String {
+        match self.processes.iter().find(|p| p.pid == pid) {
+            Some(process) => format!("{} (PID {})", process.name.to_string_lossy(), pid.as_u32()),
+            None => format!("PID {}", pid.as_u32()),
+        }
+    }
+
+    fn kill_target_label(&self) -> String {
+        let Some(process) = &self.selected_process else {
+            return "no process".to_string();
+        };
+        let name = process.name.to_string_lossy().to_string();
+        match self.kill_target {
+            KillTarget::Process => format!("{} (PID {})", name, process.pid.as_u32()),
+            KillTarget::Parent => match process.parent_pid {
+                Some(parent_pid) => format!("parent of {} (PID {})", name, parent_pid.as_u32()),
+                None => format!("parent of {}", name),
+            },
+            KillTarget::Tree => format!(
+                "process tree of {} (PID {}, {} processes)",
+                name,
+                process.pid.as_u32(),
+                self.kill_target_pids().len()
+            ),
+            KillTarget::Selection => {
+                format!("{} selected processes", self.kill_target_pids().len())
+            }
+        }
+    }
+
+    // Send a signal to every kill target, logging failures for each PID
+    fn signal_targets(&mut self, signal: KillSignal) -> Vec<(Pid, KillOutcome)> {
+        let mut results = Vec::new();
+        for pid in self.kill_target_pids() {
+            if self.target_replaced(pid) {
+                warn!(
+                    "Not sending {} to PID {}: it now belongs to another process",
+                    signal,
+                    pid.as_u32()
+                );
+                results.push((pid, KillOutcome::NoSuchProcess));
+                continue;
+            }
+            let outcome = self.system_manager.kill_process(pid, signal);
+            if !outcome.is_sent()
+                && matches!(self.kill_target, KillTarget::Tree | KillTarget::Selection)
+            {
+                error!(
+                    "Failed to send {} to {}: {}",
+                    signal,
+                    self.describe_pid(pid),
+                    outcome
+                );
+            }
+            results.push((pid, outcome));
+        }
+        results
+    }
+
+    // Send the chosen signal to the kill target, returning a description of the target.
+    // For a tree the first failure is reported, otherwise `KillOutcome::Sent`.
+    pub fn signal_selected(&mut self) -> (KillOutcome, String) {
+        let target = self.kill_target_label();
+        let results = self.signal_targets(self.kill_signal);
+        let outcome = results
+            .into_iter()
+            .map(|(_, outcome)| outcome)
+            .find(|outcome| !outcome.is_sent())
+            .unwrap_or(KillOutcome::Sent);
+        (outcome, target)
+    }
+
+    /// Send SIGTERM to the kill target and watch it for escalation to SIGKILL
+    pub fn terminate_gracefully(&mut self, now: Instant) -> (KillOutcome, String) {
+        let target = self.kill_target_label();
+        let deadline = now + Duration::from_secs(self.grace_period_secs);
+        let mut first_failure = None;
+
+        for (pid, outcome) in self.signal_targets(KillSignal::Term) {
+            if outcome.is_sent() {
+                let target = self.describe_pid(pid);
+                let start_time = self
+                    .processes
+                    .iter()
+                    .find(|p| p.pid == pid)
+                    .map_or(0, |p| p.start_time);
+                self.terminations.retain(|t| t.pid != pid);
+                self.terminations.push(Termination {
+                    pid,
+                    start_time,
+                    target,
+                    deadline,
                 });
+            } else if first_failure.is_none() {
+                first_failure = Some(outcome);
             }
-            SortBy::CpuDesc => {
-                self.processes.sort_by(|a, b| {
-                    b.cpu_usage
-                        .partial_cmp(&a.cpu_usage)
-                        .unwrap_or(std::cmp::Ordering::Equal)
+        }
+        (first_failure.unwrap_or(KillOutcome::Sent), target)
+    }
+
+    // Resolve pending terminations: exited processes are dropped, overdue ones get SIGKILL
+    pub fn check_terminations(&mut self, now: Instant) -> Option<(String, ToastType)> {
+        let mut notice = None;
+        let pending = std::mem::take(&mut self.terminations);
+
+        for termination in pending {
+            // A different start time means the PID now belongs to another process
+            let alive = self.processes.iter().any(|p| {
+                p.pid == termination.pid
+                    && p.start_time == termination.start_time
+                    && p.status != ProcessStatus::Zombie
+            });
+
+            if !alive {
+                let msg = format!("{} exited after SIGTERM", termination.target);
+                info!("{}", msg);
+                notice = Some((msg, ToastType::Success));
+            } else if now >= termination.deadline {
+                warn!(
+                    "{} still running after grace period, escalating to SIGKILL",
+                    termination.target
+                );
+                let outcome = self
+                    .system_manager
+                    .kill_process(termination.pid, KillSignal::Kill);
+                notice = Some(if outcome.is_sent() {
+                    let msg = format!("Sent SIGKILL to {}", termination.target);
+                    info!("{}", msg);
+                    (msg, ToastType::Success)
+                } else {
+                    let msg = format!("Failed to escalate {}: {}", termination.target, outcome);
+                    error!("{}", msg);
+                    (msg, ToastType::Error)
                 });
+            } else {
+                self.terminations.push(termination);
+            }
+        }
+        notice
+    }
+
+    // Open the kill confirmation for the multi-selection or the focused process
+    // PID 1 and the monitor itself are left out of a multi-selection
+    fn request_kill(&mut self) -> Effect {
+        self.focus_single_selection();
+        if self.selected_pids.len() > 1 {
+            let skipped: Vec<String> = self
+                .selected_pids
+                .iter()
+                .filter_map(|&pid| protected_pid(pid))
+                .map(String::from)
+                .collect();
+            if !skipped.is_empty() {
+                info!("Leaving {} out of the bulk kill", skipped.join(" and "));
             }
-            SortBy::MemAsc => {
-                self.processes
-                    .sort_by(|a, b| a.memory_usage.cmp(&b.memory_usage));
+            let pids: Vec<Pid> = self
+                .selected_pids
+                .iter()
+                .copied()
+                .filter(|&pid| protected_pid(pid).is_none())
+                .collect();
+            self.kill_targets = self.snapshot_targets(pids);
+            self.kill_target = KillTarget::Selection;
+            let count = self.kill_targets.len();
+            if count == 0 {
+                return self.show_toast(
+                    format!("Refusing to end {}", skipped.join(" and ")),
+                    ToastType::Error,
+                );
             }
-            SortBy::MemDesc => {
-                self.processes
-                    .sort_by(|a, b| b.memory_usage.cmp(&a.memory_usage));
+            self.kill_confirm = true;
+            warn!("Kill requested for {} selected processes", count);
+        } else if let Some(process) = &self.selected_process {
+            self.kill_confirm = true;
+            self.kill_target = KillTarget::Process;
+            warn!("Kill requested for: {:?}", process.name);
+        }
+        Effect::None
+    }
+
+    // Snapshot the subtree of the focused process for confirmation. Trees holding
+    // PID 1 or the monitor itself (so any of its ancestors) are refused.
+    fn request_kill_tree(&mut self) -> Effect {
+        if !self.single_kill_enabled() {
+            return Effect::None;
+        }
+        self.focus_single_selection();
+        let Some(process) = &self.selected_process else {
+            return Effect::None;
+        };
+        let name = process.name.to_string_lossy().to_string();
+        let pids = tree::subtree_bottom_up(&self.processes, process.pid);
+        if let Some(what) = pids.iter().find_map(|&pid| protected_pid(pid)) {
+            let msg = format!(
+                "Refusing to end the process tree of {}: it contains {}",
+                name, what
+            );
+            warn!("{}", msg);
+            return self.show_toast(msg, ToastType::Error);
+        }
+
+        self.kill_targets = self.snapshot_targets(pids);
+        self.kill_confirm = true;
+        self.kill_target = KillTarget::Tree;
+        warn!("Kill requested for process tree of: {:?}", name);
+        Effect::None
+    }
+
+    /// Evaluate alert rules against the latest refresh, logging whatever fired or
+    /// resolved and running the actions of rules that fired
+    pub fn check_alerts(&mut self, now: Instant) -> Option<(String, ToastType)> {
+        let inputs = AlertInputs {
+            processes: &self.processes,
+            sample: self.system_manager.history().latest(),
+            disks: self.disk_manager.disks(),
+            context: QueryContext {
+                cpu_cores: self.system_manager.cpu_cores,
+                total_memory: self.system_manager.total_memory,
+            },
+        };
+        let events = self.alert_engine.evaluate(&inputs, now);
+
+        for event in &events {
+            match event.kind {
+                AlertKind::Fired => {
+                    warn!("Alert fired: {}", event.message);
+                    self.run_alert_actions(event);
+                }
+                AlertKind::Resolved => info!("Alert resolved: {}", event.message),
+                AlertKind::Action => {}
             }
         }
+        events.last().and_then(|event| match event.kind {
+            AlertKind::Fired => Some((format!("Alert: {}", event.message), ToastType::Error)),
+            AlertKind::Resolved => {
+                Some((format!("Resolved: {}", event.message), ToastType::Success))
+            }
+            AlertKind::Action => None,
+        })
     }
 
-    // Kill the parent of the instance
-    pub fn kill_selected_parent(&mut self) -> (bool, String) {
-        let target_process = self.selected_process.as_ref();
+    /// Run each action of the rule behind `fired`, or only describe it for dry-run rules
+    fn run_alert_actions(&mut self, fired: &AlertEvent) {
+        let Some(rule) = self.alert_engine.rules().get(fired.rule_index).cloned() else {
+            return;
+        };
+        for action in &rule.actions {
+            let result = if rule.dry_run {
+                let target = fired
+                    .pid
+                    .map(|pid| format!(" for {}", self.describe_pid(pid)))
+                    .unwrap_or_default();
+                Ok(format!("[dry run] would {}{}", action, target))
+            } else {
+                self.run_alert_action(action, &rule, fired)
+            };
+            let outcome = match result {
+                Ok(done) => {
+                    info!("Alert rule '{}': {}", rule.name, done);
+                    done
+                }
+                Err(e) => {
+                    error!("Alert rule '{}' failed to {}: {}", rule.name, action, e);
+                    format!("failed to {}: {}", action, e)
+                }
+            };
+            self.alert_engine
+                .record_action(fired, format!("{}: {}", rule.name, outcome));
+        }
+    }
 
-        if let Some(process) = target_process {
-            let name = process.name.to_string_lossy().to_string();
-            // Check if there is a parent PID
-            if let Some(parent_pid) = process.parent_pid {
-                // Attempt to kill the parent
-                if self.system_manager.kill_process(parent_pid) {
-                    return (true, name);
+    fn run_alert_action(
+        &mut self,
+        action: &AlertAction,
+        rule: &AlertRule,
+        fired: &AlertEvent,
+    ) -> Result<String, String> {
+        let target = || {
+            fired
+                .pid
+                .ok_or_else(|| String::from("the rule does not measure a process"))
+        };
+        match action {
+            AlertAction::Signal { signal } => {
+                let pid = target()?;
+                let label = self.describe_pid(pid);
+                match self.system_manager.kill_process(pid, *signal) {
+                    KillOutcome::Sent => Ok(format!("sent {} to {}", signal, label)),
+                    outcome => Err(format!("{} ({})", outcome, label)),
+                }
+            }
+            AlertAction::Renice { nice } => {
+                let pid = target()?;
+                let label = self.describe_pid(pid);
+                self.system_manager
+                    .renice_process(pid, *nice)
+                    .map(|()| format!("set nice {} on {}", nice, label))
+                    .map_err(|e| format!("{} ({})", e, label))
+            }
+            AlertAction::Command { command } => {
+                let process = fired
+                    .pid
+                    .and_then(|pid| self.processes.iter().find(|p| p.pid == pid));
+                let env = alerts::action_environment(rule, fired, process);
+                system::run_shell_command(command, &env, None)
+                    .map(|pid| format!("started `{}` (PID {})", command, pid))
+                    .map_err(|e| e.to_string())
+            }
+            AlertAction::Snapshot { directory } => {
+                let directory = directory
+                    .clone()
+                    .or_else(alerts::default_snapshot_dir)
+                    .ok_or_else(|| String::from("no snapshot directory"))?;
+                fs::create_dir_all(&directory)
+                    .map_err(|e| format!("failed to create {}: {}", directory.display(), e))?;
+                let slug: String = rule
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() {
+                            c.to_ascii_lowercase()
+                        } else {
+                            '-'
+                        }
+                    })
+                    .collect();
+                let path = directory.join(format!("snapshot-{}-{}.csv", fired.timestamp, slug));
+                let file = fs::File::create(&path)
+                    .map_err(|e| format!("failed to create {}: {}", path.display(), e))?;
+                write_csv(
+                    file,
+                    &self.processes,
+                    self.system_manager.cpu_cores,
+                    self.system_manager.total_memory,
+                )?;
+                Ok(format!("saved snapshot to {}", path.display()))
+            }
+        }
+    }
+
+    /// Restart watched processes that have gone, logging each step
+    pub fn check_watchdog(&mut self, now: Instant) -> Option<(String, ToastType)> {
+        let mut notice = None;
+        for event in self.watchdog.check(&self.processes, now) {
+            match event {
+                WatchEvent::Lost { index, retry_in } => {
+                    let entry = &self.watchdog.entries()[index];
+                    warn!(
+                        "Watchdog: process with {} is gone, restarting in {}s",
+                        entry.target,
+                        retry_in.as_secs()
+                    );
+                }
+                WatchEvent::Restart { index, attempt } => {
+                    let entry = self.watchdog.entries()[index].clone();
+                    let env = [(String::from("TARNER_WATCH_ATTEMPT"), attempt.to_string())];
+                    match system::run_shell_command(
+                        &entry.restart_command,
+                        &env,
+                        entry.working_dir.as_deref(),
+                    ) {
+                        Ok(pid) => {
+                            let msg = format!(
+                                "Watchdog restarted process with {} (attempt {} of {}, PID {})",
+                                entry.target, attempt, entry.max_restarts, pid
+                            );
+                            info!("{}: `{}`", msg, entry.restart_command);
+                            notice = Some((msg, ToastType::Success));
+                        }
+                        Err(e) => {
+                            let msg = format!(
+                                "Watchdog failed to run `{}` (attempt {} of {}): {}",
+                                entry.restart_command, attempt, entry.max_restarts, e
+                            );
+                            error!("{}", msg);
+                            notice = Some((msg, ToastType::Error));
+                        }
+                    }
+                }
+                WatchEvent::GaveUp { index } => {
+                    let entry = &self.watchdog.entries()[index];
+                    let msg = format!(
+                        "Watchdog gave up on process with {} after {} restarts",
+                        entry.target, entry.max_restarts
+                    );
+                    error!("{}", msg);
+                    notice = Some((msg, ToastType::Error));
                 }
             }
-            return (false, name);
         }
-        (false, "".to_string())
+        notice
+    }
+
+    fn show_toast(&mut self, msg: String, style: ToastType) -> Effect {
+        self.toast = Some((msg, style));
+        Effect::HideToast(TOAST_DURATION)
+    }
+
+    fn search_mode_changed(&mut self) {
+        self.update_query();
+        info!("Search mode: {}", self.search_options.describe());
+        self.save_settings();
+    }
+
+    fn save_settings(&self) {
+        if self.persist_settings {
+            AppSettings {
+                theme: self.theme,
+                grace_period_secs: self.grace_period_secs,
+                history_retention_secs: self.history_retention_secs,
+                sort: self.current_sort.clone(),
+                columns: self.columns.clone(),
+                search: self.search_options,
+                presets: self.presets.clone(),
+                alert_rules: self.alert_engine.rules().to_vec(),
+                watchdog: self.watchdog.entries().to_vec(),
+            }
+            .save();
+        }
     }
 
     pub fn run_with_settings() -> iced::Result {
@@ -257,21 +1167,31 @@ impl TarnerMonitor {
     }
 }
 
-async fn export_action(
-    processes: Vec<ProcessInfo>,
+/// Number of samples that covers `retention_secs` at the refresh rate
+fn history_samples(retention_secs: u64) -> usize {
+    (retention_secs / REFRESH_INTERVAL.as_secs()).max(1) as usize
+}
+
+/// Why `pid` must never be part of a tree or bulk kill: it is PID 1 or the
+/// monitor itself
+fn protected_pid(pid: Pid) -> Option<&'static str> {
+    if pid == Pid::from_u32(1) {
+        Some("PID 1")
+    } else if pid == Pid::from_u32(std::process::id()) {
+        Some("Tarner Monitor itself")
+    } else {
+        None
+    }
+}
+
+/// Write `processes` as CSV, one row per process with a header row first
+pub fn write_csv<W: io::Write>(
+    output: W,
+    processes: &[ProcessInfo],
     cpu_cores: usize,
     total_memory: u64,
-) -> Result<String, String> {
-    let Some(mut path) = dirs::download_dir() else {
-        return Err("Could not find download directory.".to_string());
-    };
-    path.push("tarner_monitor_export.csv");
-    let file_path_str = path.to_string_lossy().to_string();
-
-    let mut writer = match csv::Writer::from_path(&path) {
-        Ok(w) => w,
-        Err(e) => return Err(format!("Failed to create file: {}", e)),
-    };
+) -> Result<(), String> {
+    let mut writer = csv::Writer::from_writer(output);
 
     writer
         .write_record([
@@ -285,9 +1205,23 @@ async fn export_action(
             "Disk Read (bytes)",
             "Disk Write (bytes)",
             "Runtime (sec)",
+            "User",
+            "UID",
+            "GID",
+            "Threads",
+            "Start Time (unix)",
+            "Executable",
+            "Working Directory",
+            "Command Line",
         ])
         .map_err(|e| format!("Failed to write header: {}", e))?;
 
+    let path = |value: &Option<PathBuf>| {
+        value
+            .as_ref()
+            .map_or_else(String::new, |p| p.display().to_string())
+    };
+
     for p in processes {
         let cpu_percent = p.cpu_usage / cpu_cores as f32;
         let mem_percent = (p.memory_usage as f64 / total_memory as f64) * 100.0;
@@ -307,17 +1241,42 @@ async fn export_action(
                 p.disk_usage.read_bytes.to_string(),
                 p.disk_usage.written_bytes.to_string(),
                 p.run_time.to_string(),
+                p.user.clone().unwrap_or_default(),
+                format_optional(p.uid),
+                format_optional(p.gid),
+                p.thread_count.to_string(),
+                p.start_time.to_string(),
+                path(&p.exe),
+                path(&p.cwd),
+                p.command_line.clone(),
             ])
             .map_err(|e| format!("Failed to write record: {}", e))?;
     }
 
     writer
         .flush()
-        .map_err(|e| format!("Failed to flush CSV: {}", e))?;
+        .map_err(|e| format!("Failed to flush CSV: {}", e))
+}
+
+/// Write `processes` to tarner_monitor_export.csv in the download directory
+pub fn export_to_downloads(
+    processes: &[ProcessInfo],
+    cpu_cores: usize,
+    total_memory: u64,
+) -> Result<String, String> {
+    let Some(mut path) = dirs::download_dir() else {
+        return Err("Could not find download directory.".to_string());
+    };
+    path.push("tarner_monitor_export.csv");
+    let file_path_str = path.to_string_lossy().to_string();
+
+    let file = fs::File::create(&path).map_err(|e| format!("Failed to create file: {}", e))?;
+    write_csv(file, processes, cpu_cores, total_memory)?;
     Ok(format!("Export successful to {}", file_path_str))
 }
 
-async fn load_logs_action() -> Result<Vec<String>, String> {
+/// Lines of this session's log file
+pub fn load_logs() -> Result<Vec<String>, String> {
     let Some(log_path) = logger::get_log_path() else {
         return Err("Log path not found.".to_string());
     };
@@ -327,113 +1286,366 @@ async fn load_logs_action() -> Result<Vec<String>, String> {
         .map_err(|e| format!("Failed to read log file: {}", e))
 }
 
-impl Application for TarnerMonitor {
-    type Executor = iced::executor::Default;
-    type Message = Message;
-    type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        let app = Self::new();
-        (app, Command::none())
-    }
-
-    fn title(&self) -> String {
-        String::from("TarnerMonitor - Process Manager")
-    }
-
-    fn theme(&self) -> Self::Theme {
-        self.theme.into()
-    }
-
-    fn update(&mut self, message: Message) -> Command<Message> {
+impl TarnerMonitor {
+    /// Apply `message` to the state. Shared by the iced GUI and the terminal UI,
+    /// which differ only in how they carry out the returned Effect
+    pub fn handle(&mut self, message: Message) -> Effect {
         match message {
             Message::ProcessSelected(pid) => {
-                self.selected_process = self.processes.iter().find(|p| p.pid == pid).cloned();
-                if self.selected_process.is_some() {
-                    info!(
-                        "Selected process: {:?}",
-                        self.selected_process.as_ref().unwrap().name
-                    );
+                self.select_process(pid, self.modifiers);
+                if let Some(process) = &self.selected_process {
+                    info!("Selected process: {:?}", process.name);
+                }
+                if self.selected_pids.len() > 1 {
+                    info!("{} processes selected", self.selected_pids.len());
+                }
+                self.kill_confirm = false;
+            }
+            Message::SelectAllFiltered => {
+                self.selected_pids = self.get_filtered().iter().map(|p| p.pid).collect();
+                if self
+                    .selected_process
+                    .as_ref()
+                    .is_none_or(|p| !self.selected_pids.contains(&p.pid))
+                {
+                    let first = self.selected_pids.iter().next().copied();
+                    self.focus_process(first);
                 }
                 self.kill_confirm = false;
+                info!(
+                    "Selected all {} matching processes",
+                    self.selected_pids.len()
+                );
+            }
+            Message::ClearSelection => {
+                self.selected_pids.clear();
+                self.selection_anchor = None;
+                self.selected_process = None;
+                self.kill_confirm = false;
+                info!("Cleared selection");
             }
             Message::SearchChanged(search) => {
-                self.search_str = search;
+                self.set_search(search);
                 info!("Set process filter to: {}", self.search_str);
             }
+            Message::ToggleSearchRegex => {
+                self.search_options.regex = !self.search_options.regex;
+                self.search_mode_changed();
+            }
+            Message::ToggleSearchCase => {
+                self.search_options.case_sensitive = !self.search_options.case_sensitive;
+                self.search_mode_changed();
+            }
+            Message::ToggleSearchFullCommand => {
+                self.search_options.full_command = !self.search_options.full_command;
+                self.search_mode_changed();
+            }
+            Message::PresetSelected(name) => {
+                let Some(preset) = presets::find(&self.presets, &name).cloned() else {
+                    warn!("Filter preset '{}' no longer exists", name);
+                    return Effect::None;
+                };
+                self.search_options = preset.search_options;
+                self.set_search(preset.search);
+                self.current_sort = preset.sort;
+                self.columns = preset.columns;
+                self.apply_sort();
+                info!(
+                    "Applied filter preset '{}': {}",
+                    preset.name, self.search_str
+                );
+                self.preset_name = preset.name.clone();
+                self.active_preset = Some(preset.name);
+                self.save_settings();
+            }
+            Message::PresetNameChanged(name) => {
+                self.preset_name = name;
+            }
+            Message::SavePreset => {
+                let name = self.preset_name.trim().to_string();
+                if name.is_empty() {
+                    return self.show_toast(
+                        "Enter a name for the preset first".to_string(),
+                        ToastType::Error,
+                    );
+                }
+                presets::save(
+                    &mut self.presets,
+                    FilterPreset {
+                        name: name.clone(),
+                        search: self.search_str.clone(),
+                        search_options: self.search_options,
+                        sort: self.current_sort.clone(),
+                        columns: self.columns.clone(),
+                    },
+                );
+                info!("Saved filter preset '{}': {}", name, self.search_str);
+                self.active_preset = Some(name.clone());
+                self.save_settings();
+                return self.show_toast(format!("Saved preset '{}'", name), ToastType::Success);
+            }
+            Message::DeletePreset => {
+                if let Some(name) = self.active_preset.as_ref()
+                    && presets::remove(&mut self.presets, name)
+                {
+                    info!("Deleted filter preset '{}'", name);
+                    self.active_preset = None;
+                    self.save_settings();
+                }
+            }
+            Message::ToggleTreeMode => {
+                self.tree_mode = !self.tree_mode;
+                info!(
+                    "Switched to {} process view",
+                    if self.tree_mode { "tree" } else { "flat" }
+                );
+            }
+            Message::ToggleExpanded(pid) => {
+                if !self.collapsed.remove(&pid) {
+                    self.collapsed.insert(pid);
+                }
+            }
             Message::RequestKill => {
-                if self.selected_process.is_some() {
+                return self.request_kill();
+            }
+            Message::RequestKillParent => {
+                if !self.single_kill_enabled() {
+                    return Effect::None;
+                }
+                self.focus_single_selection();
+                if let Some(process) = &self.selected_process {
                     self.kill_confirm = true;
-                    warn!(
-                        "Kill requested for: {:?}",
-                        self.selected_process.as_ref().unwrap().name
-                    );
+                    self.kill_target = KillTarget::Parent;
+                    warn!("Kill requested for parent of: {:?}", process.name);
                 }
             }
+            Message::RequestKillTree => {
+                return self.request_kill_tree();
+            }
+            Message::SignalSelected(signal) => {
+                self.kill_signal = signal;
+                info!("Selected signal {}", signal);
+            }
             Message::ConfirmKill => {
-                let (success, name) = self.kill_selected_parent();
+                let (outcome, target) = self.signal_selected();
                 self.kill_confirm = false;
-                self.selected_process = None;
 
-                let (msg, style) = if success {
-                    let msg = format!("Successfully killed parent of {}", name);
-                    (msg, ToastType::Success)
-                } else {
-                    let msg = format!("Failed to kill parent of {}", name);
-                    (msg, ToastType::Error)
+                let signal = self.kill_signal;
+                let msg = match &outcome {
+                    KillOutcome::Sent => format!("Sent {} to {}", signal, target),
+                    KillOutcome::PermissionDenied => {
+                        format!("Permission denied sending {} to {}", signal, target)
+                    }
+                    KillOutcome::NoSuchProcess => format!("No such process: {}", target),
+                    KillOutcome::Unsupported => {
+                        format!("{} is not supported on this platform", signal)
+                    }
+                    KillOutcome::Failed(e) => {
+                        format!("Failed to send {} to {}: {}", signal, target, e)
+                    }
                 };
 
-                if success {
+                let style = if outcome.is_sent() {
                     info!("{}", msg);
+                    ToastType::Success
                 } else {
-                    error!("{}", msg)
-                }
+                    error!("{}", msg);
+                    ToastType::Error
+                };
 
-                self.toast = Some((msg, style));
+                return self.show_toast(msg, style);
+            }
+            Message::ConfirmGracefulKill => {
+                let (outcome, target) = self.terminate_gracefully(Instant::now());
+                self.kill_confirm = false;
 
-                return Command::perform(
-                    async { tokio::time::sleep(Duration::from_secs(3)).await },
-                    |_| Message::HideToast,
+                let (msg, style) = if outcome.is_sent() {
+                    let msg = format!(
+                        "Sent SIGTERM to {}, SIGKILL in {}s if still running",
+                        target, self.grace_period_secs
+                    );
+                    info!("{}", msg);
+                    (msg, ToastType::Success)
+                } else {
+                    let msg = format!("Failed to send SIGTERM to {}: {}", target, outcome);
+                    error!("{}", msg);
+                    (msg, ToastType::Error)
+                };
+                return self.show_toast(msg, style);
+            }
+            Message::CancelEscalation(pid) => {
+                if let Some(index) = self.terminations.iter().position(|t| t.pid == pid) {
+                    let termination = self.terminations.remove(index);
+                    info!("Canceled SIGKILL escalation for {}", termination.target);
+                }
+            }
+            Message::ToggleColumn(column) => {
+                columns::toggle(&mut self.columns, column);
+                self.save_settings();
+            }
+            Message::MoveColumn(column, offset) => {
+                columns::move_column(&mut self.columns, column, offset);
+                self.save_settings();
+            }
+            Message::ResizeColumn(column, delta) => {
+                columns::resize(&mut self.columns, column, delta);
+                self.save_settings();
+            }
+            Message::ToggleAlertRule(index) => {
+                if let Some(rule) = self.alert_engine.rules().get(index) {
+                    let enabled = !rule.enabled;
+                    info!(
+                        "{} alert rule '{}'",
+                        if enabled { "Enabled" } else { "Disabled" },
+                        rule.name
+                    );
+                    self.alert_engine.set_enabled(index, enabled);
+                    self.save_settings();
+                }
+            }
+            Message::ClearAlertHistory => {
+                self.alert_engine.clear_history();
+                info!("Cleared alert history");
+            }
+            Message::EventFilterChanged(filter) => {
+                self.event_query = EventFeed::parse_filter(&filter);
+                self.event_filter = filter;
+            }
+            Message::EventKindSelected(kind) => {
+                self.event_kind = kind;
+            }
+            Message::ClearEvents => {
+                self.event_feed.clear();
+                info!("Cleared process events");
+            }
+            Message::WatchSelected => {
+                let Some(process) = self.selected_process.clone() else {
+                    return Effect::None;
+                };
+                let name = process.name.to_string_lossy().to_string();
+                if process.args.is_empty() {
+                    return self.show_toast(
+                        format!(
+                            "No command line known for {}; add it on the Watchdog tab",
+                            name
+                        ),
+                        ToastType::Error,
+                    );
+                }
+                let target = WatchTarget::Name(name.clone());
+                if self.watchdog.entries().iter().any(|e| e.target == target) {
+                    return self
+                        .show_toast(format!("{} is already watched", name), ToastType::Error);
+                }
+                let mut entry = WatchEntry::new(target, system::shell_join(&process.args));
+                entry.working_dir = process.cwd.clone();
+                info!(
+                    "Watchdog: watching {} with restart command `{}`",
+                    name, entry.restart_command
                 );
+                self.watchdog.add(entry);
+                self.save_settings();
+                return self.show_toast(format!("Watching {}", name), ToastType::Success);
             }
-            Message::CancelKill => {
-                self.kill_confirm = false;
-                info!("Kill canceled");
+            Message::WatchPatternChanged(pattern) => {
+                self.watch_pattern = pattern;
             }
-            Message::SortAlpha => {
-                if self.current_sort == SortBy::AlphaAsc {
-                    self.current_sort = SortBy::AlphaDesc;
-                    info!("Sort Alphabet Descending");
+            Message::WatchCommandChanged(command) => {
+                self.watch_command = command;
+            }
+            Message::ToggleWatchByCommandLine => {
+                self.watch_by_command_line = !self.watch_by_command_line;
+            }
+            Message::AddWatch => {
+                let pattern = self.watch_pattern.trim().to_string();
+                let command = self.watch_command.trim().to_string();
+                if pattern.is_empty() || command.is_empty() {
+                    return self.show_toast(
+                        "Enter both a process to watch and a restart command".to_string(),
+                        ToastType::Error,
+                    );
+                }
+                let target = if self.watch_by_command_line {
+                    WatchTarget::CommandLine(pattern)
                 } else {
-                    self.current_sort = SortBy::AlphaAsc;
-                    info!("Sort Alphabet Ascending");
+                    WatchTarget::Name(pattern)
+                };
+                info!(
+                    "Watchdog: watching process with {} with restart command `{}`",
+                    target, command
+                );
+                self.watchdog.add(WatchEntry::new(target, command));
+                self.watch_pattern.clear();
+                self.watch_command.clear();
+                self.save_settings();
+            }
+            Message::ToggleWatch(index) => {
+                if let Some(entry) = self.watchdog.entries().get(index) {
+                    let enabled = !entry.enabled;
+                    info!(
+                        "Watchdog: {} watch on {}",
+                        if enabled { "enabled" } else { "disabled" },
+                        entry.target
+                    );
+                    self.watchdog.set_enabled(index, enabled);
+                    self.save_settings();
                 }
-                self.apply_sort();
             }
-            Message::SortCpu => {
-                if self.current_sort == SortBy::CpuAsc {
-                    self.current_sort = SortBy::CpuDesc;
-                    info!("Sort Cpu Descending");
-                } else {
-                    self.current_sort = SortBy::CpuAsc;
-                    info!("Sort Cpu Ascending");
+            Message::ResetWatch(index) => {
+                if let Some(entry) = self.watchdog.entries().get(index) {
+                    info!("Watchdog: reset restart budget for {}", entry.target);
+                    self.watchdog.reset(index);
                 }
-                self.apply_sort();
             }
-            Message::SortMem => {
-                if self.current_sort == SortBy::MemAsc {
-                    self.current_sort = SortBy::MemDesc;
-                    info!("Sort Memory Descending");
-                } else {
-                    self.current_sort = SortBy::MemAsc;
-                    info!("Sort Memory Ascending");
+            Message::RemoveWatch(index) => {
+                if let Some(entry) = self.watchdog.remove(index) {
+                    info!("Watchdog: stopped watching process with {}", entry.target);
+                    self.save_settings();
                 }
+            }
+            Message::GracePeriodChanged(secs) => {
+                self.grace_period_secs = secs.max(1);
+                info!("Set grace period to {}s", self.grace_period_secs);
+                self.save_settings();
+            }
+            Message::HistoryRetentionChanged(secs) => {
+                self.history_retention_secs = secs.max(60);
+                self.process_history
+                    .set_capacity(history_samples(self.history_retention_secs));
+                info!(
+                    "Set history retention to {} minutes",
+                    self.history_retention_secs / 60
+                );
+                self.save_settings();
+            }
+            Message::CancelKill => {
+                self.kill_confirm = false;
+                info!("Kill canceled");
+            }
+            Message::SortClicked(field) => {
+                sort::click(&mut self.current_sort, field, self.modifiers.shift());
+                let order: Vec<String> = self
+                    .current_sort
+                    .iter()
+                    .map(|key| {
+                        let direction = if key.descending { "desc" } else { "asc" };
+                        format!("{} {}", key.field.label(), direction)
+                    })
+                    .collect();
+                info!("Sort by {}", order.join(", "));
                 self.apply_sort();
+                self.save_settings();
             }
-            Message::RefreshTick(_instant) => {
+            Message::RefreshTick(instant) => {
                 self.refresh_processes();
                 self.apply_sort();
+
+                let terminations = self.check_terminations(instant);
+                let watchdog = self.check_watchdog(instant);
+                if let Some((msg, style)) = self.check_alerts(instant).or(watchdog).or(terminations)
+                {
+                    return self.show_toast(msg, style);
+                }
             }
             Message::ToggleTheme => {
                 self.theme = match self.theme {
@@ -446,31 +1658,62 @@ impl Application for TarnerMonitor {
                         AppTheme::Light
                     }
                 };
-                let settings = AppSettings { theme: self.theme };
-                settings.save();
+                self.save_settings();
             }
             Message::TabSelected(tab) => {
                 self.active_tab = tab;
                 info!("Changed Tab to {:?}", tab);
 
                 if tab == Tab::Settings {
-                    return Command::perform(load_logs_action(), Message::LogsLoaded);
+                    return Effect::LoadLogs;
                 }
             }
-            Message::ExportToCsv => {
+            Message::NiceValueChanged(nice) => {
+                self.nice_value = nice.clamp(-20, 19);
+            }
+            Message::ReniceSelected => {
+                let (reniced, total) = self.renice_selected();
+                let msg = format!(
+                    "Set nice {} on {} of {} processes",
+                    self.nice_value, reniced, total
+                );
+                let style = if reniced == total {
+                    info!("{}", msg);
+                    ToastType::Success
+                } else {
+                    error!("{}", msg);
+                    ToastType::Error
+                };
+                return self.show_toast(msg, style);
+            }
+            Message::ExportToCsv | Message::ExportSelection => {
                 self.toast = Some(("Exporting...".to_string(), ToastType::Success));
                 info!("Exporting to CSV...");
 
                 let processes_to_export: Vec<ProcessInfo> =
-                    self.get_filtered().into_iter().cloned().collect();
+                    if matches!(message, Message::ExportSelection) {
+                        let pids = self.action_pids();
+                        self.processes
+                            .iter()
+                            .filter(|p| pids.contains(&p.pid))
+                            .cloned()
+                            .collect()
+                    } else {
+                        self.get_filtered().into_iter().cloned().collect()
+                    };
 
                 let cpu_cores = self.system_manager.cpu_cores;
                 let total_memory = self.system_manager.total_memory;
 
-                return Command::perform(
-                    export_action(processes_to_export, cpu_cores, total_memory),
-                    Message::ExportFinished,
-                );
+                return Effect::Export {
+                    processes: processes_to_export,
+                    cpu_cores,
+                    total_memory,
+                };
+            }
+            Message::ChartWindowSelected(window) => {
+                self.chart_window = window;
+                info!("Showing system charts for the last {}", window.label());
             }
             Message::ExportFinished(result) => {
                 let (msg, style) = match result {
@@ -483,19 +1726,14 @@ impl Application for TarnerMonitor {
                         (format!("Error: {}", error_message), ToastType::Error)
                     }
                 };
-                self.toast = Some((msg, style));
-
-                return Command::perform(
-                    async { tokio::time::sleep(Duration::from_secs(3)).await },
-                    |_| Message::HideToast,
-                );
+                return self.show_toast(msg, style);
             }
             Message::HideToast => {
                 self.toast = None;
             }
             Message::LoadLogs => {
                 self.log_lines = vec!["Loading logs...".to_string()];
-                return Command::perform(load_logs_action(), Message::LogsLoaded);
+                return Effect::LoadLogs;
             }
             Message::LogsLoaded(result) => match result {
                 Ok(lines) => {
@@ -516,22 +1754,58 @@ impl Application for TarnerMonitor {
                 }) = event
                 {
                     // Check our app's state
-                    if self.active_tab == Tab::Processes
-                        && !self.kill_confirm
-                        && self.selected_process.is_some()
-                    {
+                    if self.active_tab == Tab::Processes && !self.kill_confirm {
                         // Trigger the kill confirmation
-                        self.kill_confirm = true;
-                        warn!(
-                            "Kill requested for: {:?}",
-                            self.selected_process.as_ref().unwrap().name
-                        );
+                        return self.request_kill();
                     }
                 }
-                return Command::none();
+                if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+                    self.modifiers = modifiers;
+                }
+                return Effect::None;
+            }
+        }
+        Effect::None
+    }
+}
+
+impl Application for TarnerMonitor {
+    type Executor = iced::executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = ();
+
+    fn new(_flags: ()) -> (Self, Command<Message>) {
+        let app = Self::new();
+        (app, Command::none())
+    }
+
+    fn title(&self) -> String {
+        String::from("TarnerMonitor - Process Manager")
+    }
+
+    fn theme(&self) -> Self::Theme {
+        self.theme.into()
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match self.handle(message) {
+            Effect::None => Command::none(),
+            Effect::HideToast(delay) => {
+                Command::perform(async move { tokio::time::sleep(delay).await }, |_| {
+                    Message::HideToast
+                })
             }
+            Effect::Export {
+                processes,
+                cpu_cores,
+                total_memory,
+            } => Command::perform(
+                async move { export_to_downloads(&processes, cpu_cores, total_memory) },
+                Message::ExportFinished,
+            ),
+            Effect::LoadLogs => Command::perform(async { load_logs() }, Message::LogsLoaded),
         }
-        Command::none()
     }
 
     fn view(&self) -> iced::Element<'_, Self::Message, Self::Theme, iced::Renderer> {
@@ -540,7 +1814,7 @@ impl Application for TarnerMonitor {
 
     fn subscription(&self) -> Subscription<Message> {
         Subscription::batch(vec![
-            iced::time::every(Duration::from_secs(1)).map(Message::RefreshTick),
+            iced::time::every(REFRESH_INTERVAL).map(Message::RefreshTick),
             event::listen().map(Message::EventOccurred),
         ])
     }
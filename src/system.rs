@@ -1,8 +1,142 @@
+use crate::disks::DiskInfo;
+use crate::history::{RingBuffer, SystemSample, Trend};
+use crate::network::NetworkInfo;
 use crate::process::ProcessInfo;
-use sysinfo::{Pid, System};
+use crate::sensors::SensorInfo;
+use crate::source::{SysinfoSource, SystemSource};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::{fmt, io, process, thread};
+use sysinfo::{Pid, Signal};
+
+/// Signals that can be sent to a process from the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KillSignal {
+    Term,
+    Kill,
+    Interrupt,
+    Hangup,
+    Stop,
+    Continue,
+    User1,
+    User2,
+}
+
+impl KillSignal {
+    pub const ALL: [KillSignal; 8] = [
+        KillSignal::Term,
+        KillSignal::Kill,
+        KillSignal::Interrupt,
+        KillSignal::Hangup,
+        KillSignal::Stop,
+        KillSignal::Continue,
+        KillSignal::User1,
+        KillSignal::User2,
+    ];
+
+    /// Parse a signal name such as `TERM`, `SIGKILL` or `usr1`; on Unix the
+    /// platform's signal number is accepted too
+    pub fn from_name(name: &str) -> Option<KillSignal> {
+        let name = name.trim().to_ascii_uppercase();
+        #[cfg(unix)]
+        if let Ok(number) = name.parse::<libc::c_int>() {
+            return KillSignal::ALL.into_iter().find(|s| s.number() == number);
+        }
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        let signal = match name {
+            "TERM" => KillSignal::Term,
+            "KILL" => KillSignal::Kill,
+            "INT" => KillSignal::Interrupt,
+            "HUP" => KillSignal::Hangup,
+            "STOP" => KillSignal::Stop,
+            "CONT" => KillSignal::Continue,
+            "USR1" => KillSignal::User1,
+            "USR2" => KillSignal::User2,
+            _ => return None,
+        };
+        Some(signal)
+    }
+}
+
+impl fmt::Display for KillSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KillSignal::Term => "SIGTERM",
+            KillSignal::Kill => "SIGKILL",
+            KillSignal::Interrupt => "SIGINT",
+            KillSignal::Hangup => "SIGHUP",
+            KillSignal::Stop => "SIGSTOP",
+            KillSignal::Continue => "SIGCONT",
+            KillSignal::User1 => "SIGUSR1",
+            KillSignal::User2 => "SIGUSR2",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(unix)]
+impl KillSignal {
+    /// The platform's number for this signal, as passed to `kill(2)`
+    pub fn number(self) -> libc::c_int {
+        match self {
+            KillSignal::Term => libc::SIGTERM,
+            KillSignal::Kill => libc::SIGKILL,
+            KillSignal::Interrupt => libc::SIGINT,
+            KillSignal::Hangup => libc::SIGHUP,
+            KillSignal::Stop => libc::SIGSTOP,
+            KillSignal::Continue => libc::SIGCONT,
+            KillSignal::User1 => libc::SIGUSR1,
+            KillSignal::User2 => libc::SIGUSR2,
+        }
+    }
+}
+
+impl From<KillSignal> for Signal {
+    fn from(signal: KillSignal) -> Self {
+        match signal {
+            KillSignal::Term => Signal::Term,
+            KillSignal::Kill => Signal::Kill,
+            KillSignal::Interrupt => Signal::Interrupt,
+            KillSignal::Hangup => Signal::Hangup,
+            KillSignal::Stop => Signal::Stop,
+            KillSignal::Continue => Signal::Continue,
+            KillSignal::User1 => Signal::User1,
+            KillSignal::User2 => Signal::User2,
+        }
+    }
+}
+
+/// Result of sending a signal to a process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KillOutcome {
+    Sent,
+    PermissionDenied,
+    NoSuchProcess,
+    Unsupported,
+    Failed(String),
+}
+
+impl KillOutcome {
+    pub fn is_sent(&self) -> bool {
+        *self == KillOutcome::Sent
+    }
+}
+
+impl fmt::Display for KillOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KillOutcome::Sent => f.write_str("signal sent"),
+            KillOutcome::PermissionDenied => f.write_str("permission denied"),
+            KillOutcome::NoSuchProcess => f.write_str("no such process"),
+            KillOutcome::Unsupported => f.write_str("signal not supported on this platform"),
+            KillOutcome::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
 
 pub struct SystemManager {
-    pub system: System,
+    source: Box<dyn SystemSource>,
     pub os_name: String,
     pub os_version: String,
     pub kernel_version: String,
@@ -10,6 +144,9 @@ pub struct SystemManager {
     pub cpu_brand: String,
     pub cpu_cores: usize,
     pub total_memory: u64,
+    /// Boot time in seconds since the Unix epoch
+    pub boot_time: u64,
+    history: RingBuffer<SystemSample>,
 }
 
 impl Default for SystemManager {
@@ -19,31 +156,25 @@ impl Default for SystemManager {
 }
 
 impl SystemManager {
+    /// Samples kept for the system charts: one hour at one refresh per second
+    pub const HISTORY_CAPACITY: usize = 3600;
+
     pub fn new() -> Self {
-        let mut system = System::new_all();
-
-        //Initial Refresh
-        system.refresh_all();
-
-        // sleep for MINIMUM_CPU_UPDATE_INTERVAL
-        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-
-        //second refresh for CPU usage
-        system.refresh_all();
-
-        let os_name = System::name().unwrap_or_else(|| String::from("N/A"));
-        let os_version = System::os_version().unwrap_or_else(|| String::from("N/A"));
-        let kernel_version = System::kernel_version().unwrap_or_else(|| String::from("N/A"));
-        let hostname = System::host_name().unwrap_or_else(|| String::from("N/A"));
-        let cpu_brand = system
-            .cpus()
-            .first()
-            .map_or("N/A".to_string(), |cpu| cpu.brand().to_string());
-        let cpu_cores = system.cpus().len();
-        let total_memory = system.total_memory();
-
-        SystemManager {
-            system,
+        Self::with_source(Box::new(SysinfoSource::new()))
+    }
+
+    pub fn with_source(source: Box<dyn SystemSource>) -> Self {
+        let os_name = source.os_name();
+        let os_version = source.os_version();
+        let kernel_version = source.kernel_version();
+        let hostname = source.hostname();
+        let cpu_brand = source.cpu_brand();
+        let cpu_cores = source.cpu_cores();
+        let total_memory = source.total_memory();
+        let boot_time = source.boot_time();
+
+        let mut manager = SystemManager {
+            source,
             os_name,
             os_version,
             kernel_version,
@@ -51,38 +182,156 @@ impl SystemManager {
             cpu_brand,
             cpu_cores,
             total_memory,
-        }
+            boot_time,
+            history: RingBuffer::new(Self::HISTORY_CAPACITY),
+        };
+        manager.record_sample();
+        manager
     }
 
     pub fn refresh(&mut self) {
-        self.system.refresh_all();
+        self.source.refresh();
+        self.record_sample();
     }
 
-    pub fn get_processes(&self) -> Vec<ProcessInfo> {
-        self.system
-            .processes()
-            .iter()
-            .map(|(pid, process)| {
-                ProcessInfo::new(
-                    process.name().to_os_string(),
-                    process.parent(),
-                    *pid,
-                    process.cpu_usage(),
-                    process.memory(),
-                    process.run_time(),
-                    process.status(),
-                    process.accumulated_cpu_time(),
-                    process.disk_usage(),
-                )
-            })
-            .collect()
+    fn record_sample(&mut self) {
+        let sample = SystemSample {
+            cpu_usage: self.source.global_cpu_usage(),
+            core_usages: self.source.cpu_usages(),
+            core_frequencies: self.source.cpu_frequencies(),
+            used_memory: self.source.used_memory(),
+            available_memory: self.source.available_memory(),
+            used_swap: self.source.used_swap(),
+            total_swap: self.source.total_swap(),
+            load_average: self.source.load_average(),
+            uptime: self.source.uptime(),
+            process_count: self.source.process_count(),
+            thread_count: self.source.thread_count(),
+        };
+        self.history.push(sample);
+    }
+
+    /// System-wide samples recorded on each refresh, oldest first
+    pub fn history(&self) -> &RingBuffer<SystemSample> {
+        &self.history
     }
 
-    pub fn kill_process(&mut self, pid: Pid) -> bool {
-        if let Some(process) = self.system.process(pid) {
-            process.kill()
-        } else {
-            false
+    /// How `metric` changed between the two most recent samples
+    pub fn trend(&self, metric: impl Fn(&SystemSample) -> f64) -> Trend {
+        let mut recent = self.history.iter().rev();
+        match (recent.next(), recent.next()) {
+            (Some(current), Some(previous)) => Trend::between(metric(previous), metric(current)),
+            _ => Trend::Steady,
         }
     }
+
+    /// Usage of one logical core over the last `samples` refreshes, oldest first
+    pub fn core_history(&self, core: usize, samples: usize) -> Vec<f32> {
+        self.history
+            .last_n(samples)
+            .map(|s| s.core_usages.get(core).copied().unwrap_or(0.0))
+            .collect()
+    }
+
+    /// Every process, without the threads some platforms list among them,
+    /// so the list and its start/exit events agree with the process count
+    pub fn get_processes(&self) -> Vec<ProcessInfo> {
+        let mut processes = self.source.processes();
+        processes.retain(|p| !p.is_thread);
+        processes
+    }
+
+    pub fn get_disks(&self) -> Vec<DiskInfo> {
+        self.source.disks()
+    }
+
+    pub fn get_networks(&self) -> Vec<NetworkInfo> {
+        self.source.networks()
+    }
+
+    pub fn get_sensors(&self) -> Vec<SensorInfo> {
+        self.source.sensors()
+    }
+
+    pub fn used_memory(&self) -> u64 {
+        self.source.used_memory()
+    }
+
+    pub fn kill_process(&mut self, pid: Pid, signal: KillSignal) -> KillOutcome {
+        self.source.kill_process(pid, signal)
+    }
+
+    /// Set the scheduling priority (nice value, -20 to 19) of a process
+    pub fn renice_process(&mut self, pid: Pid, nice: i32) -> io::Result<()> {
+        self.source.renice(pid, nice.clamp(-20, 19))
+    }
+}
+
+/// Join `args` into a command line for the platform shell used by
+/// [`run_shell_command`], quoting each argument so it arrives unchanged
+pub fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(not(windows))]
+fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(windows)]
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        arg.to_string()
+    } else {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    }
+}
+
+/// Start `command` in the platform shell without waiting for it, optionally in
+/// `working_dir`; its output is discarded and the exit status is logged from a
+/// background thread. Returns the shell's PID.
+pub fn run_shell_command(
+    command: &str,
+    env: &[(String, String)],
+    working_dir: Option<&Path>,
+) -> io::Result<u32> {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    if let Some(dir) = working_dir {
+        shell.current_dir(dir);
+    }
+    let mut child = shell
+        .arg(command)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(process::Stdio::null())
+        // Inherited output would be drawn over the terminal UI
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .spawn()?;
+    let pid = child.id();
+    let command = command.to_string();
+    thread::spawn(move || match child.wait() {
+        Ok(status) if status.success() => info!("Command `{}` finished", command),
+        Ok(status) => warn!("Command `{}` exited with {}", command, status),
+        Err(e) => warn!("Failed to wait for command `{}`: {}", command, e),
+    });
+    Ok(pid)
 }
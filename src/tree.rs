@@ -0,0 +1,206 @@
+use crate::process::ProcessInfo;
+use std::collections::{HashMap, HashSet};
+use sysinfo::Pid;
+
+/// Map each parent PID to the PIDs of its direct children
+pub fn children_map(processes: &[ProcessInfo]) -> HashMap<Pid, Vec<Pid>> {
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for process in processes {
+        if let Some(parent_pid) = process.parent_pid
+            && parent_pid != process.pid
+        {
+            children.entry(parent_pid).or_default().push(process.pid);
+        }
+    }
+    children
+}
+
+/// `root` and all of its descendants, children before their parents.
+///
+/// The root comes last so a tree can be terminated bottom-up without
+/// orphaning anything. PIDs are visited once even if the parent links loop.
+pub fn subtree_bottom_up(processes: &[ProcessInfo], root: Pid) -> Vec<Pid> {
+    let children = children_map(processes);
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    visit_post_order(&children, root, &mut visited, &mut order);
+    order
+}
+
+fn visit_post_order(
+    children: &HashMap<Pid, Vec<Pid>>,
+    pid: Pid,
+    visited: &mut HashSet<Pid>,
+    order: &mut Vec<Pid>,
+) {
+    if !visited.insert(pid) {
+        return;
+    }
+    if let Some(kids) = children.get(&pid) {
+        for &child in kids {
+            visit_post_order(children, child, visited, order);
+        }
+    }
+    order.push(pid);
+}
+
+/// One visible row of the hierarchical process list
+pub struct TreeRow<'a> {
+    pub process: &'a ProcessInfo,
+    pub depth: usize,
+    pub has_children: bool,
+    pub expanded: bool,
+    /// CPU usage of the process plus all of its descendants
+    pub subtree_cpu: f32,
+    /// Memory usage of the process plus all of its descendants
+    pub subtree_memory: u64,
+}
+
+/// Flatten `processes` into indented tree rows.
+///
+/// Only PIDs in `visible` are shown, so callers pass the search matches plus
+/// their ancestors. Siblings keep the order they have in `processes`, which
+/// means sorting the list sorts each level of the tree. Children of PIDs in
+/// `collapsed` are hidden but still counted in the subtree totals.
+pub fn build_rows<'a>(
+    processes: &'a [ProcessInfo],
+    visible: &HashSet<Pid>,
+    collapsed: &HashSet<Pid>,
+) -> Vec<TreeRow<'a>> {
+    let by_pid: HashMap<Pid, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+    let children = children_map(processes);
+
+    let mut totals = HashMap::new();
+    for process in processes {
+        subtree_totals(
+            process.pid,
+            &by_pid,
+            &children,
+            &mut totals,
+            &mut HashSet::new(),
+        );
+    }
+
+    let mut rows = Vec::new();
+    let mut placed = HashSet::new();
+    let is_root = |p: &ProcessInfo| match p.parent_pid {
+        Some(parent_pid) => parent_pid == p.pid || !visible.contains(&parent_pid),
+        None => true,
+    };
+
+    for process in processes.iter().filter(|p| visible.contains(&p.pid)) {
+        if is_root(process) {
+            push_rows(
+                process,
+                0,
+                &by_pid,
+                &children,
+                visible,
+                collapsed,
+                &totals,
+                &mut placed,
+                &mut rows,
+            );
+        }
+    }
+    rows
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_rows<'a>(
+    process: &'a ProcessInfo,
+    depth: usize,
+    by_pid: &HashMap<Pid, &'a ProcessInfo>,
+    children: &HashMap<Pid, Vec<Pid>>,
+    visible: &HashSet<Pid>,
+    collapsed: &HashSet<Pid>,
+    totals: &HashMap<Pid, (f32, u64)>,
+    placed: &mut HashSet<Pid>,
+    rows: &mut Vec<TreeRow<'a>>,
+) {
+    if !placed.insert(process.pid) {
+        return;
+    }
+
+    let visible_children: Vec<&ProcessInfo> = children
+        .get(&process.pid)
+        .into_iter()
+        .flatten()
+        .filter(|pid| visible.contains(pid))
+        .filter_map(|pid| by_pid.get(pid).copied())
+        .collect();
+    let expanded = !collapsed.contains(&process.pid);
+    let (subtree_cpu, subtree_memory) = totals[&process.pid];
+
+    rows.push(TreeRow {
+        process,
+        depth,
+        has_children: !visible_children.is_empty(),
+        expanded,
+        subtree_cpu,
+        subtree_memory,
+    });
+
+    if expanded {
+        for child in visible_children {
+            push_rows(
+                child,
+                depth + 1,
+                by_pid,
+                children,
+                visible,
+                collapsed,
+                totals,
+                placed,
+                rows,
+            );
+        }
+    }
+}
+
+fn subtree_totals(
+    pid: Pid,
+    by_pid: &HashMap<Pid, &ProcessInfo>,
+    children: &HashMap<Pid, Vec<Pid>>,
+    totals: &mut HashMap<Pid, (f32, u64)>,
+    in_progress: &mut HashSet<Pid>,
+) -> (f32, u64) {
+    if let Some(total) = totals.get(&pid) {
+        return *total;
+    }
+    // A parent link loop contributes nothing further
+    if !in_progress.insert(pid) {
+        return (0.0, 0);
+    }
+
+    let (mut cpu, mut memory) = by_pid
+        .get(&pid)
+        .map_or((0.0, 0), |p| (p.cpu_usage, p.memory_usage));
+    if let Some(kids) = children.get(&pid) {
+        for &child in kids {
+            let (child_cpu, child_memory) =
+                subtree_totals(child, by_pid, children, totals, in_progress);
+            cpu += child_cpu;
+            memory += child_memory;
+        }
+    }
+    totals.insert(pid, (cpu, memory));
+    (cpu, memory)
+}
+
+/// `matches` plus every ancestor of a match, so the tree keeps its path to the root
+pub fn with_ancestors(processes: &[ProcessInfo], matches: &[&ProcessInfo]) -> HashSet<Pid> {
+    let parents: HashMap<Pid, Option<Pid>> =
+        processes.iter().map(|p| (p.pid, p.parent_pid)).collect();
+    let mut visible = HashSet::new();
+    for process in matches {
+        let mut current = Some(process.pid);
+        while let Some(pid) = current {
+            if !visible.insert(pid) {
+                break;
+            }
+            current = parents.get(&pid).copied().flatten();
+        }
+    }
+    visible
+}
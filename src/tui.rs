@@ -0,0 +1,693 @@
+//! Terminal frontend in the style of top/htop, for use over SSH.
+//!
+//! Drives the same TarnerMonitor as the iced GUI: every key press becomes a
+//! Message passed to TarnerMonitor::handle, and the returned Effect is carried
+//! out inline. Only the Processes and System tabs are drawn.
+
+use crate::columns::ProcessColumn;
+use crate::format::{format_duration, format_megabytes, format_optional, format_unix_time};
+use crate::process::ProcessInfo;
+use crate::sort::SortField;
+use crate::state::{
+    Effect, Message, REFRESH_INTERVAL, Tab, TarnerMonitor, ToastType, export_to_downloads,
+    load_logs,
+};
+use crate::system::KillSignal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Sparkline, Table, TableState, Tabs, Wrap,
+};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::time::Instant;
+
+const HELP: &str = "↑↓ select  / search  <> sort  I invert  t tree  ␣ fold  ⏎ details  k kill  e export  Tab tabs  q quit";
+
+/// Keyboard-driven terminal UI around a TarnerMonitor
+pub struct Tui {
+    pub monitor: TarnerMonitor,
+    /// Keys go to the search box until Enter or Esc
+    pub searching: bool,
+    pub show_details: bool,
+    pub quit: bool,
+    toast_until: Option<Instant>,
+    table: TableState,
+}
+
+impl Tui {
+    pub fn new(monitor: TarnerMonitor) -> Self {
+        Tui {
+            monitor,
+            searching: false,
+            show_details: false,
+            quit: false,
+            toast_until: None,
+            table: TableState::default(),
+        }
+    }
+
+    /// Take over the terminal until the user quits
+    pub fn run(mut self) -> io::Result<()> {
+        let mut terminal = ratatui::init();
+        let result = self.event_loop(&mut terminal);
+        ratatui::restore();
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut next_tick = Instant::now() + REFRESH_INTERVAL;
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let timeout = next_tick.saturating_duration_since(Instant::now());
+            if event::poll(timeout)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key);
+            }
+
+            let now = Instant::now();
+            if now >= next_tick {
+                self.dispatch(Message::RefreshTick(now));
+                next_tick = now + REFRESH_INTERVAL;
+            }
+            if self.toast_until.is_some_and(|until| now >= until) {
+                self.toast_until = None;
+                self.dispatch(Message::HideToast);
+            }
+        }
+        Ok(())
+    }
+
+    /// Update the monitor, then run whatever it asked for until nothing is left
+    pub fn dispatch(&mut self, message: Message) {
+        let mut next = Some(message);
+        while let Some(message) = next.take() {
+            next = match self.monitor.handle(message) {
+                Effect::None => None,
+                Effect::HideToast(delay) => {
+                    self.toast_until = Some(Instant::now() + delay);
+                    None
+                }
+                Effect::Export {
+                    processes,
+                    cpu_cores,
+                    total_memory,
+                } => Some(Message::ExportFinished(export_to_downloads(
+                    &processes,
+                    cpu_cores,
+                    total_memory,
+                ))),
+                Effect::LoadLogs => Some(Message::LogsLoaded(load_logs())),
+            };
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+        } else if self.monitor.kill_confirm {
+            self.confirm_key(key);
+        } else if self.searching {
+            self.search_key(key);
+        } else {
+            self.normal_key(key);
+        }
+    }
+
+    fn confirm_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => self.dispatch(Message::ConfirmKill),
+            KeyCode::Char('g') => self.dispatch(Message::ConfirmGracefulKill),
+            KeyCode::Char('n') | KeyCode::Esc => self.dispatch(Message::CancelKill),
+            KeyCode::Left | KeyCode::Right => {
+                let signals = KillSignal::ALL;
+                let index = signals
+                    .iter()
+                    .position(|s| *s == self.monitor.kill_signal)
+                    .unwrap_or(0);
+                let index = if key.code == KeyCode::Left {
+                    (index + signals.len() - 1) % signals.len()
+                } else {
+                    (index + 1) % signals.len()
+                };
+                self.dispatch(Message::SignalSelected(signals[index]));
+            }
+            _ => {}
+        }
+    }
+
+    fn search_key(&mut self, key: KeyEvent) {
+        let mut search = self.monitor.search_str().to_string();
+        match key.code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                search.clear();
+            }
+            KeyCode::Backspace => {
+                search.pop();
+            }
+            KeyCode::Char(c) => search.push(c),
+            _ => {}
+        }
+        if search != self.monitor.search_str() {
+            self.dispatch(Message::SearchChanged(search));
+        }
+    }
+
+    fn normal_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Tab | KeyCode::BackTab => {
+                let tab = if self.monitor.active_tab == Tab::System {
+                    Tab::Processes
+                } else {
+                    Tab::System
+                };
+                self.dispatch(Message::TabSelected(tab));
+            }
+            KeyCode::Char('1') => self.dispatch(Message::TabSelected(Tab::Processes)),
+            KeyCode::Char('2') => self.dispatch(Message::TabSelected(Tab::System)),
+            _ if self.monitor.active_tab == Tab::System => {}
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-20),
+            KeyCode::PageDown => self.move_selection(20),
+            KeyCode::Home => self.select_row(0),
+            KeyCode::End => self.select_row(usize::MAX),
+            KeyCode::Char('<') => self.cycle_sort(-1),
+            KeyCode::Char('>') => self.cycle_sort(1),
+            KeyCode::Char('I') => {
+                if let Some(key) = self.monitor.current_sort.first() {
+                    self.dispatch(Message::SortClicked(key.field));
+                }
+            }
+            KeyCode::Char('t') => self.dispatch(Message::ToggleTreeMode),
+            KeyCode::Char(' ') if self.monitor.tree_mode => {
+                if let Some(process) = &self.monitor.selected_process {
+                    self.dispatch(Message::ToggleExpanded(process.pid));
+                }
+            }
+            KeyCode::Enter => self.show_details = !self.show_details,
+            KeyCode::Char('k') | KeyCode::Delete | KeyCode::F(9) => {
+                self.dispatch(Message::RequestKill)
+            }
+            KeyCode::Char('e') => self.dispatch(Message::ExportToCsv),
+            KeyCode::Esc => self.dispatch(Message::ClearSelection),
+            _ => {}
+        }
+    }
+
+    /// Move the selection by `offset` rows; with nothing selected, Up starts
+    /// from the bottom and Down from the top
+    fn move_selection(&mut self, offset: isize) {
+        let pids = self.monitor.visible_pids();
+        let current = self
+            .monitor
+            .selected_process
+            .as_ref()
+            .and_then(|selected| pids.iter().position(|pid| *pid == selected.pid));
+        let index = match current {
+            Some(index) => index.saturating_add_signed(offset),
+            None if offset < 0 => usize::MAX,
+            None => 0,
+        };
+        self.select_row(index);
+    }
+
+    /// Select the row at `index`, clamped to the list
+    fn select_row(&mut self, index: usize) {
+        let pids = self.monitor.visible_pids();
+        if let Some(last) = pids.len().checked_sub(1) {
+            self.dispatch(Message::ProcessSelected(pids[index.min(last)]));
+        }
+    }
+
+    /// Sort by the previous or next field, in the order the columns are shown
+    fn cycle_sort(&mut self, step: isize) {
+        let fields = self.sort_fields();
+        let current = self
+            .monitor
+            .current_sort
+            .first()
+            .and_then(|key| fields.iter().position(|field| *field == key.field))
+            .unwrap_or(0);
+        let next = (current as isize + step).rem_euclid(fields.len() as isize) as usize;
+        self.dispatch(Message::SortClicked(fields[next]));
+    }
+
+    fn sort_fields(&self) -> Vec<SortField> {
+        std::iter::once(SortField::Name)
+            .chain(
+                self.monitor
+                    .columns
+                    .iter()
+                    .map(|layout| SortField::Column(layout.column)),
+            )
+            .collect()
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [tabs, body, status] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let system = self.monitor.active_tab == Tab::System;
+        frame.render_widget(
+            Tabs::new(["1 Processes", "2 System"])
+                .select(usize::from(system))
+                .highlight_style(Style::new().reversed()),
+            tabs,
+        );
+
+        if system {
+            self.draw_system(frame, body);
+        } else {
+            self.draw_processes(frame, body);
+        }
+
+        let status_line = match &self.monitor.toast {
+            Some((msg, ToastType::Success)) => Line::from(msg.as_str()).green(),
+            Some((msg, ToastType::Error)) => Line::from(msg.as_str()).red(),
+            None => Line::from(HELP).dim(),
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+
+        if self.monitor.kill_confirm {
+            self.draw_kill_confirm(frame);
+        }
+    }
+
+    fn draw_processes(&mut self, frame: &mut Frame, area: Rect) {
+        let details_height = if self.show_details && self.monitor.selected_process.is_some() {
+            11
+        } else {
+            0
+        };
+        let [search, table, details] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(details_height),
+        ])
+        .areas(area);
+
+        // Search box, then the query error or search mode
+        let cursor = if self.searching { "▏" } else { "" };
+        let mut search_line = vec![
+            Span::from("Search: ").bold(),
+            Span::from(format!("{}{}", self.monitor.search_str(), cursor)),
+            Span::from("   "),
+        ];
+        search_line.push(match self.monitor.query() {
+            Err(error) => Span::from(format!("Filter error: {}", error)).red(),
+            Ok(_) => Span::from(format!(
+                "Search mode: {}",
+                self.monitor.search_options().describe()
+            ))
+            .dim(),
+        });
+        frame.render_widget(Paragraph::new(Line::from(search_line)), search);
+
+        self.draw_table(frame, table);
+
+        if details_height > 0
+            && let Some(process) = &self.monitor.selected_process
+        {
+            self.draw_details(frame, details, process);
+        }
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let monitor = &self.monitor;
+        let cpu_cores = monitor.system_manager.cpu_cores;
+        let total_memory = monitor.system_manager.total_memory;
+
+        // Name first, then the configured columns, with the sort arrows of the GUI
+        let header_label = |field: SortField| {
+            let keys = &monitor.current_sort;
+            match keys.iter().position(|key| key.field == field) {
+                Some(index) => {
+                    let arrow = if keys[index].descending { "▼" } else { "▲" };
+                    if keys.len() > 1 {
+                        format!("{} {}{}", field.label(), arrow, index + 1)
+                    } else {
+                        format!("{} {}", field.label(), arrow)
+                    }
+                }
+                None => field.label().to_string(),
+            }
+        };
+        let header = Row::new(self.sort_fields().into_iter().map(header_label)).bold();
+
+        let mut widths = vec![Constraint::Length(24)];
+        widths.extend(monitor.columns.iter().map(|layout| {
+            if layout.column == ProcessColumn::CommandLine {
+                return Constraint::Fill(1);
+            }
+            // Roughly ten pixels per character in the GUI
+            let chars = (layout.width / 10.0).round() as u16;
+            Constraint::Length(chars.max(layout.column.label().len() as u16 + 2))
+        }));
+
+        let row = |process: &ProcessInfo, name: String| {
+            let selected = monitor.selected_pids.contains(&process.pid);
+            let cells =
+                std::iter::once(Cell::from(name)).chain(monitor.columns.iter().map(|layout| {
+                    Cell::from(layout.column.value(process, cpu_cores, total_memory))
+                }));
+            let row = Row::new(cells);
+            if selected { row.underlined() } else { row }
+        };
+
+        let rows: Vec<Row> = if monitor.tree_mode {
+            monitor
+                .tree_rows()
+                .iter()
+                .map(|tree_row| {
+                    let marker = match (tree_row.has_children, tree_row.expanded) {
+                        (false, _) => "  ",
+                        (true, true) => "▾ ",
+                        (true, false) => "▸ ",
+                    };
+                    let name = format!(
+                        "{}{}{}",
+                        "  ".repeat(tree_row.depth),
+                        marker,
+                        tree_row.process.name.to_string_lossy()
+                    );
+                    row(tree_row.process, name)
+                })
+                .collect()
+        } else {
+            monitor
+                .get_filtered()
+                .into_iter()
+                .map(|process| row(process, process.name.to_string_lossy().into_owned()))
+                .collect()
+        };
+        let count = rows.len();
+
+        let selected = monitor.selected_process.as_ref().and_then(|selected| {
+            monitor
+                .visible_pids()
+                .iter()
+                .position(|pid| *pid == selected.pid)
+        });
+        self.table.select(selected);
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(1)
+            .row_highlight_style(Style::new().reversed())
+            .block(Block::new().borders(Borders::TOP).title(format!(
+                " {} of {} processes ",
+                count,
+                monitor.processes.len()
+            )));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect, process: &ProcessInfo) {
+        let cpu_cores = self.monitor.system_manager.cpu_cores.max(1);
+        let total_memory = self.monitor.system_manager.total_memory.max(1);
+        let path = |path: &Option<std::path::PathBuf>| {
+            path.as_ref()
+                .map_or_else(|| "N/A".to_string(), |p| p.display().to_string())
+        };
+        let field = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::from(format!("{:<14}", label)).bold(),
+                Span::from(value),
+            ])
+        };
+
+        let lines = vec![
+            field(
+                "PID:",
+                format!(
+                    "{}   Parent PID: {}   Status: {}",
+                    process.pid.as_u32(),
+                    process
+                        .parent_pid
+                        .map_or_else(|| "N/A".to_string(), |p| p.as_u32().to_string()),
+                    process.status
+                ),
+            ),
+            field(
+                "CPU:",
+                format!(
+                    "{:.2}%   Accumulated: {} ms   Threads: {}",
+                    process.cpu_usage / cpu_cores as f32,
+                    process.acc_cpu_time,
+                    process.thread_count
+                ),
+            ),
+            field(
+                "Memory:",
+                format!(
+                    "{} ({:.2}%)   Virtual: {}",
+                    format_megabytes(process.memory_usage),
+                    process.memory_usage as f64 / total_memory as f64 * 100.0,
+                    format_megabytes(process.virtual_memory)
+                ),
+            ),
+            field(
+                "Disk:",
+                format!(
+                    "read {}/{} bytes, written {}/{} bytes (new/total)",
+                    process.disk_usage.read_bytes,
+                    process.disk_usage.total_read_bytes,
+                    process.disk_usage.written_bytes,
+                    process.disk_usage.total_written_bytes
+                ),
+            ),
+            field(
+                "User:",
+                format!(
+                    "{} ({}/{})",
+                    process.user_label(),
+                    format_optional(process.uid),
+                    format_optional(process.gid)
+                ),
+            ),
+            field(
+                "Started:",
+                format!(
+                    "{}   Runtime: {}",
+                    format_unix_time(process.start_time),
+                    format_duration(process.run_time)
+                ),
+            ),
+            field("Command line:", process.command_line.clone()),
+            field("Executable:", path(&process.exe)),
+            field("Working dir:", path(&process.cwd)),
+        ];
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .title(format!(" {} ", process.name.to_string_lossy()));
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_kill_confirm(&self, frame: &mut Frame) {
+        let Some(process) = &self.monitor.selected_process else {
+            return;
+        };
+        let (title, description) = self.monitor.kill_prompt(process);
+        let mut lines = vec![Line::from(description)];
+        for target in self.monitor.kill_preview().iter().take(8) {
+            lines.push(Line::from(format!(
+                "  {}  {}",
+                target.pid.as_u32(),
+                target.name.to_string_lossy()
+            )));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::from("Signal: "),
+            Span::from(format!("◀ {} ▶", self.monitor.kill_signal)).bold(),
+        ]));
+        lines.push(Line::from(format!(
+            "y send {}   g terminate gracefully (SIGKILL after {}s)   n cancel",
+            self.monitor.kill_signal, self.monitor.grace_period_secs
+        )));
+
+        let height = lines.len() as u16 + 2;
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(format!(" {} ", title)).red()),
+            area,
+        );
+    }
+
+    fn draw_system(&self, frame: &mut Frame, area: Rect) {
+        let manager = &self.monitor.system_manager;
+        let Some(latest) = manager.history().latest() else {
+            return;
+        };
+        let cores = latest.core_usages.len();
+        let [info, gauges, chart] = Layout::vertical([
+            Constraint::Length(11),
+            Constraint::Length(cores as u16 + 2),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        let field = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::from(format!("{:<16}", label)).bold(),
+                Span::from(value),
+            ])
+        };
+        let trend =
+            |metric: fn(&crate::history::SystemSample) -> f64| manager.trend(metric).arrow();
+        let load = latest.load_average;
+        let lines = vec![
+            field("OS:", format!("{} {}", manager.os_name, manager.os_version)),
+            field("Kernel Version:", manager.kernel_version.clone()),
+            field("Hostname:", manager.hostname.clone()),
+            field(
+                "CPU:",
+                format!(
+                    "{} ({} logical cores)",
+                    manager.cpu_brand, manager.cpu_cores
+                ),
+            ),
+            field(
+                "Load:",
+                format!(
+                    "{:.2} {} {:.2} {} {:.2} {}",
+                    load.one,
+                    trend(|s| s.load_average.one),
+                    load.five,
+                    trend(|s| s.load_average.five),
+                    load.fifteen,
+                    trend(|s| s.load_average.fifteen)
+                ),
+            ),
+            field(
+                "Tasks:",
+                format!(
+                    "{} processes {}, {} threads {}",
+                    latest.process_count,
+                    trend(|s| s.process_count as f64),
+                    latest.thread_count,
+                    trend(|s| s.thread_count as f64)
+                ),
+            ),
+            field(
+                "Memory:",
+                format!(
+                    "{} used / {} total",
+                    format_megabytes(latest.used_memory),
+                    format_megabytes(manager.total_memory)
+                ),
+            ),
+            field(
+                "Swap:",
+                format!(
+                    "{} used / {} total",
+                    format_megabytes(latest.used_swap),
+                    format_megabytes(latest.total_swap)
+                ),
+            ),
+            field("Uptime:", format_duration(latest.uptime)),
+            field("Boot Time:", format_unix_time(manager.boot_time)),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .title(" System Information "),
+            ),
+            info,
+        );
+
+        // One bar per core, then memory and swap
+        let mut bars = vec![Constraint::Length(1); cores + 2];
+        bars.push(Constraint::Min(0));
+        let rows = Layout::vertical(bars).split(gauges);
+        let gauge = |label: String, ratio: f64, color: Color| {
+            Gauge::default()
+                .gauge_style(Style::new().fg(color).bg(Color::DarkGray))
+                .ratio(ratio.clamp(0.0, 1.0))
+                .label(label)
+                .add_modifier(Modifier::BOLD)
+        };
+        for (core, usage) in latest.core_usages.iter().enumerate() {
+            frame.render_widget(
+                gauge(
+                    format!("CPU {} {:.1}%", core, usage),
+                    f64::from(*usage) / 100.0,
+                    Color::Cyan,
+                ),
+                rows[core],
+            );
+        }
+        frame.render_widget(
+            gauge(
+                format!(
+                    "Memory {:.1}%",
+                    ratio(latest.used_memory, manager.total_memory) * 100.0
+                ),
+                ratio(latest.used_memory, manager.total_memory),
+                Color::Magenta,
+            ),
+            rows[cores],
+        );
+        frame.render_widget(
+            gauge(
+                format!(
+                    "Swap {:.1}%",
+                    ratio(latest.used_swap, latest.total_swap) * 100.0
+                ),
+                ratio(latest.used_swap, latest.total_swap),
+                Color::Yellow,
+            ),
+            rows[cores + 1],
+        );
+
+        // Total CPU over the last refreshes, as wide as the terminal allows
+        let samples: Vec<u64> = manager
+            .history()
+            .last_n(chart.width as usize)
+            .map(|s| s.cpu_usage.round() as u64)
+            .collect();
+        frame.render_widget(
+            Sparkline::default()
+                .block(
+                    Block::new()
+                        .borders(Borders::TOP)
+                        .title(format!(" CPU Total: {:.1}% ", latest.cpu_usage)),
+                )
+                .data(&samples)
+                .max(100)
+                .style(Style::new().cyan()),
+            chart,
+        );
+    }
+}
+
+fn ratio(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64
+    }
+}
@@ -1,37 +1,48 @@
-use crate::state::{AppTheme, Message, Tab, TarnerMonitor, ToastType};
-use iced::widget::{Column, button, column, container, row, scrollable, text, text_input};
+use crate::alerts::{AlertKind, RuleStatus};
+use crate::chart::{self, LineChart};
+use crate::columns::{self, ProcessColumn};
+use crate::events::LifecycleKind;
+use crate::format::{
+    format_duration, format_megabytes, format_optional, format_rate, format_unix_time,
+};
+use crate::history::Trend;
+use crate::process::ProcessInfo;
+use crate::sensors::{self, SensorLevel};
+use crate::sort::SortField;
+use crate::state::{
+    AppTheme, ChartWindow, KillTarget, Message, REFRESH_INTERVAL, Tab, TarnerMonitor, ToastType,
+};
+use crate::system::KillSignal;
+use crate::watchdog::WatchStatus;
+use iced::widget::{
+    Column, Space, button, checkbox, column, container, pick_list, progress_bar, row, scrollable,
+    text, text_input,
+};
 use iced::{Alignment, Color, Element, Length, Theme};
 
 pub fn view<'a>(state: &'a TarnerMonitor, theme: Theme) -> Element<'a, Message> {
-    let tab_buttons = row![
-        button("Processes")
-            .on_press(Message::TabSelected(Tab::Processes))
-            .style(if state.active_tab == Tab::Processes {
-                iced::theme::Button::Primary
-            } else {
-                iced::theme::Button::Secondary
-            }),
-        button("System")
-            .on_press(Message::TabSelected(Tab::System))
-            .style(if state.active_tab == Tab::System {
-                iced::theme::Button::Primary
-            } else {
-                iced::theme::Button::Secondary
-            }),
-        button("Settings")
-            .on_press(Message::TabSelected(Tab::Settings))
-            .style(if state.active_tab == Tab::Settings {
-                iced::theme::Button::Primary
-            } else {
-                iced::theme::Button::Secondary
-            })
-    ]
-    .spacing(5);
+    let mut tab_buttons = row![].spacing(5);
+    for tab in Tab::ALL {
+        tab_buttons = tab_buttons.push(
+            button(tab.label())
+                .on_press(Message::TabSelected(tab))
+                .style(if state.active_tab == tab {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                }),
+        );
+    }
 
     // Choose content based on the active tab
     let tab_content = match state.active_tab {
         Tab::Processes => view_processes(state),
         Tab::System => view_system(state),
+        Tab::Disks => view_disks(state),
+        Tab::Network => view_network(state),
+        Tab::Alerts => view_alerts(state),
+        Tab::Events => view_events(state),
+        Tab::Watchdog => view_watchdog(state),
         Tab::Settings => view_settings(state, theme.clone()),
     };
 
@@ -66,53 +77,180 @@ pub fn view<'a>(state: &'a TarnerMonitor, theme: Theme) -> Element<'a, Message>
 }
 
 pub fn view_processes<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
-    let search_input = text_input("Search processes...", &state.search_str)
-        .on_input(Message::SearchChanged)
-        .padding(10);
+    let search_input = text_input(
+        "Search or filter, e.g. cpu>20 mem>500MB user:root name~^ssh",
+        state.search_str(),
+    )
+    .on_input(Message::SearchChanged)
+    .padding(10);
+
+    // Search mode toggles, highlighted while active
+    let mode_button = |label, active: bool, message| {
+        button(label).on_press(message).style(if active {
+            iced::theme::Button::Primary
+        } else {
+            iced::theme::Button::Secondary
+        })
+    };
+    let options = state.search_options();
+    let search_modes = row![
+        mode_button(".*", options.regex, Message::ToggleSearchRegex),
+        mode_button("Aa", options.case_sensitive, Message::ToggleSearchCase),
+        mode_button(
+            "Cmd",
+            options.full_command,
+            Message::ToggleSearchFullCommand
+        ),
+    ]
+    .spacing(2);
 
     let end_task_button = button("End Task (Del)")
         .on_press(Message::RequestKill)
         .style(iced::theme::Button::Destructive);
 
-    let sort_buttons = row![
-        button("Name").on_press(Message::SortAlpha),
-        button("CPU").on_press(Message::SortCpu),
-        button("Mem").on_press(Message::SortMem),
-    ]
-    .spacing(5);
+    // Parent and tree kills need a single process, so a multi-selection disables them
+    let single_kill = state.single_kill_enabled();
+    let end_parent_button = button("End Parent")
+        .on_press_maybe(single_kill.then_some(Message::RequestKillParent))
+        .style(iced::theme::Button::Destructive);
 
-    let controls = row![search_input, end_task_button, sort_buttons]
-        .spacing(10)
-        .padding(10);
+    let end_tree_button = button("End Tree")
+        .on_press_maybe(single_kill.then_some(Message::RequestKillTree))
+        .style(iced::theme::Button::Destructive);
 
-    let header = row![
-        text("Process Name").width(Length::FillPortion(3)),
-        text("PID").width(Length::FillPortion(1)),
-        text("CPU %").width(Length::FillPortion(1)),
-        text("Memory %").width(Length::FillPortion(1)),
+    let select_all_button = button("Select All")
+        .on_press(Message::SelectAllFiltered)
+        .style(iced::theme::Button::Secondary);
+
+    let tree_toggle = button(if state.tree_mode {
+        "Flat View"
+    } else {
+        "Tree View"
+    })
+    .on_press(Message::ToggleTreeMode)
+    .style(iced::theme::Button::Secondary);
+
+    let controls = row![
+        search_input,
+        search_modes,
+        end_task_button,
+        end_parent_button,
+        end_tree_button,
+        select_all_button,
+        tree_toggle,
     ]
     .spacing(10)
     .padding(10);
 
+    // Saved filters: pick one to restore its search, sort and columns
+    let preset_names: Vec<String> = state.presets.iter().map(|p| p.name.clone()).collect();
+    let preset_bar = row![
+        text("Preset:"),
+        pick_list(
+            preset_names,
+            state.active_preset.clone(),
+            Message::PresetSelected
+        )
+        .placeholder("Choose a saved filter"),
+        text_input("Preset name", &state.preset_name)
+            .on_input(Message::PresetNameChanged)
+            .on_submit(Message::SavePreset)
+            .width(Length::Fixed(200.0)),
+        button("Save Preset")
+            .on_press(Message::SavePreset)
+            .style(iced::theme::Button::Positive),
+        button("Delete")
+            .on_press_maybe(state.active_preset.as_ref().map(|_| Message::DeletePreset))
+            .style(iced::theme::Button::Destructive),
+    ]
+    .spacing(10)
+    .padding([0, 10])
+    .align_items(Alignment::Center);
+
+    // Shows the active search mode, or the query error while the list falls
+    // back to a plain substring search
+    let search_status: Element<'a, Message> = match state.query() {
+        Err(error) => text(format!("Filter error: {}", error))
+            .style(Color::from_rgb(0.8, 0.0, 0.0))
+            .into(),
+        Ok(_) => text(format!("Search mode: {}", options.describe()))
+            .size(12)
+            .into(),
+    };
+
+    // Bulk actions for the current selection
+    let selection_bar: Element<'a, Message> =
+        if state.selected_process.is_some() || !state.selected_pids.is_empty() {
+            let count = state.action_pids().len();
+            row![
+                text(format!("{} selected", count)),
+                text("Nice:"),
+                button("-").on_press(Message::NiceValueChanged(state.nice_value - 1)),
+                text(state.nice_value.to_string()),
+                button("+").on_press(Message::NiceValueChanged(state.nice_value + 1)),
+                button("Renice").on_press(Message::ReniceSelected),
+                button("Export Selected")
+                    .on_press(Message::ExportSelection)
+                    .style(iced::theme::Button::Positive),
+                button("Watch")
+                    .on_press_maybe(
+                        state
+                            .selected_process
+                            .as_ref()
+                            .map(|_| Message::WatchSelected)
+                    )
+                    .style(iced::theme::Button::Secondary),
+                button("Clear Selection")
+                    .on_press(Message::ClearSelection)
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .spacing(10)
+            .padding([0, 10])
+            .align_items(Alignment::Center)
+            .into()
+        } else {
+            Space::with_height(Length::Shrink).into()
+        };
+
+    // Header cells sort on click; shift-click adds a secondary key
+    let mut header = row![sort_header(state, SortField::Name).width(Length::FillPortion(3))]
+        .spacing(10)
+        .padding(10);
+    for layout in &state.columns {
+        header = header.push(
+            sort_header(state, SortField::Column(layout.column)).width(Length::Fixed(layout.width)),
+        );
+    }
+    let header = header.push(text("CPU history").width(Length::Fixed(80.0)));
+
     let details_pane: Element<'a, Message> = if state.kill_confirm {
         if let Some(process) = &state.selected_process {
-            let parent_pid_str = process
-                .parent_pid
-                .map_or_else(|| "N/A".to_string(), |pid| pid.as_u32().to_string());
+            let (title, description) = state.kill_prompt(process);
+
+            // Every PID a tree or bulk kill will reach, in the order signals are sent
+            let mut affected = Column::new().spacing(2);
+            if matches!(state.kill_target, KillTarget::Tree | KillTarget::Selection) {
+                for target in state.kill_preview() {
+                    affected = affected.push(text(format!(
+                        "{}  {}",
+                        target.pid.as_u32(),
+                        target.name.to_string_lossy()
+                    )));
+                }
+            }
 
             let confirm_content = column![
-                text(format!(
-                    "End parent process of '{}'?",
-                    process.name.to_string_lossy()
-                ))
-                .size(20),
-                text(format!(
-                    "This will kill the parent (PID: {}) of the selected process (PID: {}).",
-                    parent_pid_str,
-                    process.pid.as_u32()
-                )),
+                text(title).size(20),
+                text(description),
+                scrollable(affected).height(Length::Shrink),
                 row![
-                    button("Yes, End Task")
+                    text("Signal:"),
+                    pick_list(
+                        &KillSignal::ALL[..],
+                        Some(state.kill_signal),
+                        Message::SignalSelected
+                    ),
+                    button(text(format!("Yes, Send {}", state.kill_signal)))
                         .on_press(Message::ConfirmKill)
                         .style(iced::theme::Button::Destructive),
                     button("Cancel")
@@ -120,6 +258,13 @@ pub fn view_processes<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
                         .style(iced::theme::Button::Secondary)
                 ]
                 .spacing(10)
+                .align_items(Alignment::Center),
+                button(text(format!(
+                    "Terminate Gracefully (SIGTERM, SIGKILL after {}s)",
+                    state.grace_period_secs
+                )))
+                .on_press(Message::ConfirmGracefulKill)
+                .style(iced::theme::Button::Primary)
             ]
             .spacing(10)
             .padding(10)
@@ -157,6 +302,17 @@ pub fn view_processes<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
             .padding(2)
         };
 
+        // Long values such as paths get the full width of the pane
+        let wide_row = |label: &str, value: &str| {
+            row![
+                text(label).width(Length::FillPortion(1)),
+                text(if value.is_empty() { "N/A" } else { value }.to_string())
+                    .width(Length::FillPortion(3)),
+            ]
+            .spacing(10)
+            .padding(2)
+        };
+
         let details_column = column![
             text("Process Details").size(20),
             row![
@@ -203,51 +359,165 @@ pub fn view_processes<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
                 )
                 .width(Length::FillPortion(1)),
             ],
+            row![
+                detail_row(
+                    "User (UID/GID):",
+                    format!(
+                        "{} ({}/{})",
+                        process.user_label(),
+                        format_optional(process.uid),
+                        format_optional(process.gid)
+                    )
+                )
+                .width(Length::FillPortion(1)),
+                detail_row("Threads:", process.thread_count.to_string())
+                    .width(Length::FillPortion(1)),
+            ],
+            row![
+                detail_row("Started:", format_unix_time(process.start_time))
+                    .width(Length::FillPortion(1)),
+                Space::with_width(Length::FillPortion(1)),
+            ],
+            wide_row("Command line:", &process.command_line),
+            wide_row(
+                "Executable:",
+                &process
+                    .exe
+                    .as_ref()
+                    .map_or_else(|| "N/A".to_string(), |path| path.display().to_string())
+            ),
+            wide_row(
+                "Working dir:",
+                &process
+                    .cwd
+                    .as_ref()
+                    .map_or_else(|| "N/A".to_string(), |path| path.display().to_string())
+            ),
         ]
         .spacing(5)
         .padding(10)
         .width(Length::Fill);
 
-        details_column.into()
+        // Resource history charts for the selected process
+        let charts: Element<'a, Message> = match state.process_history(process.pid) {
+            Some(history) => {
+                let cores = state.system_manager.cpu_cores as f32;
+                let slots = history.capacity();
+                let cpu: Vec<f32> = history.iter().map(|s| s.cpu_usage / cores).collect();
+                let memory: Vec<f32> = history.iter().map(|s| s.memory_usage as f32).collect();
+                let read: Vec<f32> = history.iter().map(|s| s.disk_read as f32).collect();
+                let written: Vec<f32> = history.iter().map(|s| s.disk_written as f32).collect();
+                let minutes = slots as u64 * REFRESH_INTERVAL.as_secs() / 60;
+
+                let chart_cell = |label: String, chart: LineChart| {
+                    column![text(label), chart.view(Length::Fill, Length::Fixed(80.0))]
+                        .spacing(2)
+                        .width(Length::FillPortion(1))
+                };
+
+                row![
+                    chart_cell(
+                        format!("CPU % (last {} min)", minutes),
+                        LineChart::new(slots)
+                            .series(cpu, chart::CPU_COLOR)
+                            .max(100.0)
+                    ),
+                    chart_cell(
+                        "Memory".to_string(),
+                        LineChart::new(slots).series(memory, chart::MEMORY_COLOR)
+                    ),
+                    chart_cell(
+                        "Disk read / write".to_string(),
+                        LineChart::new(slots)
+                            .series(read, chart::READ_COLOR)
+                            .series(written, chart::WRITE_COLOR)
+                    ),
+                ]
+                .spacing(10)
+                .padding([0, 10])
+                .into()
+            }
+            None => Space::with_height(Length::Shrink).into(),
+        };
+
+        column![details_column, charts].into()
     } else {
         text("").into()
     };
 
-    let filtered = state.get_filtered();
     let mut process_list = Column::new().spacing(2);
 
-    for process in filtered {
-        let cpu_percent = process.cpu_usage / state.system_manager.cpu_cores as f32;
-        let mem_percent =
-            (process.memory_usage as f64 / state.system_manager.total_memory as f64) * 100.0;
+    if state.tree_mode {
+        for tree_row in state.tree_rows() {
+            let process = tree_row.process;
+            let toggle: Element<'a, Message> = if tree_row.has_children {
+                button(text(if tree_row.expanded { "▾" } else { "▸" }))
+                    .on_press(Message::ToggleExpanded(process.pid))
+                    .style(iced::theme::Button::Text)
+                    .padding(0)
+                    .width(Length::Fixed(20.0))
+                    .into()
+            } else {
+                Space::with_width(Length::Fixed(20.0)).into()
+            };
 
-        let is_selected = state.selected_process.as_ref().map(|p| p.pid) == Some(process.pid);
+            let name_cell = row![
+                Space::with_width(Length::Fixed(tree_row.depth as f32 * 16.0)),
+                toggle,
+                text(process.name.to_string_lossy()),
+            ]
+            .spacing(2)
+            .width(Length::FillPortion(3));
 
-        let process_row = button(
+            // Collapsed nodes show the totals of their whole subtree
+            let row = if tree_row.has_children && !tree_row.expanded {
+                let totals = ProcessInfo {
+                    cpu_usage: tree_row.subtree_cpu,
+                    memory_usage: tree_row.subtree_memory,
+                    ..process.clone()
+                };
+                process_row(state, &totals, name_cell.into())
+            } else {
+                process_row(state, process, name_cell.into())
+            };
+            process_list = process_list.push(row);
+        }
+    } else {
+        for process in state.get_filtered() {
+            let name_cell = text(process.name.to_string_lossy()).width(Length::FillPortion(3));
+            process_list = process_list.push(process_row(state, process, name_cell.into()));
+        }
+    }
+
+    // Countdown for processes waiting on SIGKILL escalation
+    let now = std::time::Instant::now();
+    let mut terminations = Column::new().spacing(5);
+    for termination in &state.terminations {
+        terminations = terminations.push(
             row![
-                text(process.name.to_string_lossy()).width(Length::FillPortion(3)),
-                text(format!("{}", process.pid.as_u32())).width(Length::FillPortion(1)),
-                text(format!("{:.2}", cpu_percent)).width(Length::FillPortion(1)),
-                text(format!("{:.2}", mem_percent)).width(Length::FillPortion(1)),
+                text(format!(
+                    "Terminating {}: SIGKILL in {}s",
+                    termination.target,
+                    termination.remaining_secs(now)
+                ))
+                .width(Length::Fill),
+                button("Cancel Escalation")
+                    .on_press(Message::CancelEscalation(termination.pid))
+                    .style(iced::theme::Button::Secondary),
             ]
             .spacing(10)
-            .padding(5),
-        )
-        .on_press(Message::ProcessSelected(process.pid))
-        .style(if is_selected {
-            iced::theme::Button::Primary
-        } else {
-            iced::theme::Button::Secondary
-        })
-        .width(Length::Fill);
-
-        process_list = process_list.push(process_row);
+            .align_items(Alignment::Center),
+        );
     }
 
     let content = column![
         controls,
+        container(search_status).padding([0, 10]),
+        preset_bar,
+        selection_bar,
         header,
         scrollable(process_list).height(Length::Fill),
+        terminations.padding([0, 10]),
         details_pane,
     ]
     .spacing(10);
@@ -259,6 +529,71 @@ pub fn view_processes<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
         .into()
 }
 
+/// Header button showing the sort direction, plus the key's rank when sorting by several
+fn sort_header<'a>(
+    state: &'a TarnerMonitor,
+    field: SortField,
+) -> iced::widget::Button<'a, Message> {
+    let position = state.current_sort.iter().position(|key| key.field == field);
+    let label = match position {
+        Some(index) => {
+            let key = state.current_sort[index];
+            let arrow = if key.descending { "▼" } else { "▲" };
+            if state.current_sort.len() > 1 {
+                format!("{} {}{}", field.label(), arrow, index + 1)
+            } else {
+                format!("{} {}", field.label(), arrow)
+            }
+        }
+        None => field.label().to_string(),
+    };
+    button(text(label))
+        .on_press(Message::SortClicked(field))
+        .style(iced::theme::Button::Text)
+        .padding(0)
+}
+
+fn process_row<'a>(
+    state: &'a TarnerMonitor,
+    process: &ProcessInfo,
+    name_cell: Element<'a, Message>,
+) -> Element<'a, Message> {
+    let is_selected = state.selected_pids.contains(&process.pid)
+        || state.selected_process.as_ref().map(|p| p.pid) == Some(process.pid);
+
+    let cores = state.system_manager.cpu_cores as f32;
+    let cpu_history: Vec<f32> = state
+        .process_history(process.pid)
+        .map(|history| history.last_n(60).map(|s| s.cpu_usage / cores).collect())
+        .unwrap_or_default();
+
+    let mut cells = row![name_cell].spacing(10).padding(5);
+    for layout in &state.columns {
+        let value = layout.column.value(
+            process,
+            state.system_manager.cpu_cores,
+            state.system_manager.total_memory,
+        );
+        // Fixed height keeps long command lines from wrapping onto extra lines
+        cells = cells.push(
+            text(value)
+                .width(Length::Fixed(layout.width))
+                .height(Length::Fixed(20.0)),
+        );
+    }
+    let cells = cells.push(chart::sparkline(cpu_history, 100.0, chart::CPU_COLOR));
+
+    button(cells)
+        .on_press(Message::ProcessSelected(process.pid))
+        .style(if is_selected {
+            iced::theme::Button::Primary
+        } else {
+            iced::theme::Button::Secondary
+        })
+        .width(Length::Fill)
+        .into()
+}
+
 fn view_system<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
     // Helper to create styled rows
     let detail_row = |label: &str, value: String| {
@@ -268,8 +603,6 @@ fn view_system<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
     };
 
     // Get system info from the system_manager
-    let sys = &state.system_manager.system;
-
     let os_name = state.system_manager.os_name.to_string();
     let os_version = state.system_manager.os_version.to_string();
     let kernel = state.system_manager.kernel_version.to_string();
@@ -279,7 +612,7 @@ fn view_system<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
 
     // Convert memory from bytes to Megabytes (MB) for readability
     let total_mem_mb = state.system_manager.total_memory / 1024 / 1024;
-    let used_mem_mb = sys.used_memory() / 1024 / 1024;
+    let used_mem_mb = state.system_manager.used_memory() / 1024 / 1024;
 
     let content = column![
         text("System Information").size(24),
@@ -291,6 +624,9 @@ fn view_system<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
         detail_row("Logical Cores:", cpu_cores),
         detail_row("Total Memory:", format!("{} MB", total_mem_mb)),
         detail_row("Used Memory:", format!("{} MB", used_mem_mb)),
+        view_system_health(state),
+        view_system_charts(state),
+        view_sensors(state),
     ]
     .spacing(10)
     .padding(10);
@@ -299,6 +635,827 @@ fn view_system<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
     scrollable(content).height(Length::Fill).into()
 }
 
+fn view_system_health<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let manager = &state.system_manager;
+    let Some(latest) = manager.history().latest() else {
+        return Space::with_height(Length::Shrink).into();
+    };
+
+    // Value followed by an arrow showing its direction since the previous refresh
+    let trend_row = |label: &str, value: String, trend: Trend| {
+        let color = match trend {
+            Trend::Up => Color::from_rgb(0.8, 0.0, 0.0),
+            Trend::Down => Color::from_rgb(0.0, 0.7, 0.0),
+            Trend::Steady => Color::from_rgb(0.7, 0.7, 0.7),
+        };
+        row![
+            text(label).width(Length::Fixed(150.0)),
+            text(value),
+            text(trend.arrow()).style(color),
+        ]
+        .spacing(10)
+        .padding(2)
+    };
+
+    let load = latest.load_average;
+    column![
+        text("Health").size(24),
+        trend_row(
+            "Load (1 min):",
+            format!("{:.2}", load.one),
+            manager.trend(|s| s.load_average.one)
+        ),
+        trend_row(
+            "Load (5 min):",
+            format!("{:.2}", load.five),
+            manager.trend(|s| s.load_average.five)
+        ),
+        trend_row(
+            "Load (15 min):",
+            format!("{:.2}", load.fifteen),
+            manager.trend(|s| s.load_average.fifteen)
+        ),
+        trend_row(
+            "Processes:",
+            latest.process_count.to_string(),
+            manager.trend(|s| s.process_count as f64)
+        ),
+        trend_row(
+            "Threads:",
+            latest.thread_count.to_string(),
+            manager.trend(|s| s.thread_count as f64)
+        ),
+        row![
+            text("Uptime:").width(Length::Fixed(150.0)),
+            text(format_duration(latest.uptime)),
+        ]
+        .spacing(10)
+        .padding(2),
+        row![
+            text("Boot Time:").width(Length::Fixed(150.0)),
+            text(format_unix_time(manager.boot_time)),
+        ]
+        .spacing(10)
+        .padding(2),
+    ]
+    .spacing(2)
+    .into()
+}
+
+fn view_system_charts<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let window = state.chart_window;
+    let slots = window.samples();
+    let history = state.system_manager.history();
+    let samples: Vec<_> = history.last_n(slots).collect();
+
+    let chart_block = |title: String, chart: LineChart| {
+        column![
+            text(title).size(16),
+            chart.view(Length::Fill, Length::Fixed(100.0))
+        ]
+        .spacing(4)
+    };
+
+    let latest = history.latest();
+
+    // Total CPU
+    let cpu: Vec<f32> = samples.iter().map(|s| s.cpu_usage).collect();
+    let cpu_chart = chart_block(
+        format!(
+            "CPU Total: {:.1}%",
+            latest.map_or(0.0, |sample| sample.cpu_usage)
+        ),
+        LineChart::new(slots)
+            .series(cpu, chart::CPU_COLOR)
+            .max(100.0),
+    );
+
+    // One line per logical core
+    let cores = latest.map_or(0, |sample| sample.core_usages.len());
+    let mut per_core = LineChart::new(slots).max(100.0);
+    for core in 0..cores {
+        let values = state.system_manager.core_history(core, slots);
+        per_core = per_core.series(values, chart::series_color(core));
+    }
+    let per_core_chart = chart_block(format!("Per-core CPU ({} cores)", cores), per_core);
+
+    // Memory: used and available
+    let total_memory = state.system_manager.total_memory as f32;
+    let used: Vec<f32> = samples.iter().map(|s| s.used_memory as f32).collect();
+    let available: Vec<f32> = samples.iter().map(|s| s.available_memory as f32).collect();
+    let memory_chart = chart_block(
+        format!(
+            "Memory: {} MB used / {} MB available",
+            latest.map_or(0, |s| s.used_memory) / 1024 / 1024,
+            latest.map_or(0, |s| s.available_memory) / 1024 / 1024
+        ),
+        LineChart::new(slots)
+            .series(used, chart::MEMORY_COLOR)
+            .series(available, chart::READ_COLOR)
+            .max(total_memory),
+    );
+
+    // Swap
+    let total_swap = latest.map_or(0, |s| s.total_swap);
+    let swap: Vec<f32> = samples.iter().map(|s| s.used_swap as f32).collect();
+    let swap_chart = chart_block(
+        format!(
+            "Swap: {} MB / {} MB",
+            latest.map_or(0, |s| s.used_swap) / 1024 / 1024,
+            total_swap / 1024 / 1024
+        ),
+        LineChart::new(slots)
+            .series(swap, chart::SWAP_COLOR)
+            .max(total_swap.max(1) as f32),
+    );
+
+    column![
+        text("Usage History").size(24),
+        chart_window_buttons(window),
+        cpu_chart,
+        per_core_chart,
+        view_core_grid(state),
+        memory_chart,
+        swap_chart,
+    ]
+    .spacing(10)
+    .into()
+}
+
+fn chart_window_buttons<'a>(window: ChartWindow) -> Element<'a, Message> {
+    let mut window_buttons = row![text("Window:")]
+        .spacing(5)
+        .align_items(Alignment::Center);
+    for option in ChartWindow::ALL {
+        window_buttons = window_buttons.push(
+            button(option.label())
+                .on_press(Message::ChartWindowSelected(option))
+                .style(if option == window {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                }),
+        );
+    }
+    window_buttons.into()
+}
+
+/// Cores shown side by side in the per-core grid
+const CORE_GRID_COLUMNS: usize = 4;
+
+/// Usage above this is highlighted so single-thread saturation stands out
+const CORE_SATURATED: f32 = 90.0;
+
+fn view_core_grid<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let slots = state.chart_window.samples();
+    let Some(latest) = state.system_manager.history().latest() else {
+        return text("No per-core data available").into();
+    };
+
+    let mut grid = column![text("Per-core Usage and Frequency").size(16)].spacing(8);
+    let mut current_row = row![].spacing(8);
+    for (core, usage) in latest.core_usages.iter().copied().enumerate() {
+        let frequency = latest.core_frequencies.get(core).copied().unwrap_or(0);
+        let usage_color = if usage >= CORE_SATURATED {
+            Color::from_rgb(0.8, 0.0, 0.0)
+        } else {
+            chart::series_color(core)
+        };
+
+        let cell = column![
+            text(format!("CPU {}", core)).size(14),
+            row![
+                text(format!("{:.1}%", usage)).style(usage_color),
+                text(format!("{} MHz", frequency)),
+            ]
+            .spacing(10),
+            LineChart::new(slots)
+                .series(
+                    state.system_manager.core_history(core, slots),
+                    chart::series_color(core)
+                )
+                .max(100.0)
+                .view(Length::Fill, Length::Fixed(40.0)),
+        ]
+        .spacing(2);
+        current_row = current_row.push(container(cell).padding(4).width(Length::FillPortion(1)));
+
+        if (core + 1) % CORE_GRID_COLUMNS == 0 {
+            grid = grid.push(current_row);
+            current_row = row![].spacing(8);
+        }
+    }
+
+    // Pad the last row so its cells keep the same width as the others
+    let remainder = latest.core_usages.len() % CORE_GRID_COLUMNS;
+    if remainder != 0 {
+        for _ in remainder..CORE_GRID_COLUMNS {
+            current_row = current_row.push(Space::with_width(Length::FillPortion(1)));
+        }
+        grid = grid.push(current_row);
+    }
+
+    grid.into()
+}
+
+fn view_sensors<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let sensors = state.sensor_manager.sensors();
+    if sensors.is_empty() {
+        return column![
+            text("Temperatures").size(24),
+            text("No temperature sensors available on this machine"),
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let celsius = |value: Option<f32>| value.map_or(String::from("-"), |v| format!("{:.1} °C", v));
+
+    let header = row![
+        text("Sensor").width(Length::FillPortion(3)),
+        text("Current").width(Length::FillPortion(1)),
+        text("Max").width(Length::FillPortion(1)),
+        text("Critical").width(Length::FillPortion(1)),
+        text("History").width(Length::Fixed(80.0)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let mut table = column![text("Temperatures").size(24), header].spacing(2);
+    for sensor in sensors {
+        let color = match sensor.level() {
+            SensorLevel::Critical => Color::from_rgb(0.8, 0.0, 0.0),
+            SensorLevel::Warning => Color::from_rgb(0.9, 0.6, 0.0),
+            SensorLevel::Normal => Color::from_rgb(0.0, 0.7, 0.0),
+            SensorLevel::Unknown => Color::from_rgb(0.7, 0.7, 0.7),
+        };
+        let history: Vec<f32> = state
+            .sensor_manager
+            .history(&sensor.label)
+            .map(|h| h.last_n(60).copied().collect())
+            .unwrap_or_default();
+        let scale = sensor.critical.unwrap_or(sensors::DEFAULT_CRITICAL);
+
+        table = table.push(
+            row![
+                text(&sensor.label).width(Length::FillPortion(3)),
+                text(celsius(sensor.temperature))
+                    .style(color)
+                    .width(Length::FillPortion(1)),
+                text(celsius(sensor.max)).width(Length::FillPortion(1)),
+                text(celsius(sensor.critical)).width(Length::FillPortion(1)),
+                chart::sparkline(history, scale, color),
+            ]
+            .spacing(10)
+            .padding(5)
+            .align_items(Alignment::Center),
+        );
+    }
+    table.into()
+}
+
+/// Filesystems fuller than this are shown in red
+const DISK_NEARLY_FULL: f32 = 90.0;
+
+fn view_disks<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let disks = state.disk_manager.disks();
+    if disks.is_empty() {
+        return container(text("No mounted filesystems found"))
+            .padding(10)
+            .into();
+    }
+
+    let gigabytes = |bytes: u64| format!("{:.1} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0);
+
+    let header = row![
+        text("Mount").width(Length::FillPortion(3)),
+        text("Device").width(Length::FillPortion(3)),
+        text("Type").width(Length::FillPortion(1)),
+        text("Total").width(Length::FillPortion(1)),
+        text("Available").width(Length::FillPortion(1)),
+        text("Usage").width(Length::FillPortion(3)),
+        text("Removable").width(Length::FillPortion(1)),
+        text("Read").width(Length::FillPortion(1)),
+        text("Write").width(Length::FillPortion(1)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let mut table = column![header].spacing(2);
+    for disk in disks {
+        let usage = disk.usage_percent();
+        let usage_color = if usage >= DISK_NEARLY_FULL {
+            Color::from_rgb(0.8, 0.0, 0.0)
+        } else {
+            Color::from_rgb(0.0, 0.7, 0.0)
+        };
+        table = table.push(
+            row![
+                text(disk.mount_point.display().to_string()).width(Length::FillPortion(3)),
+                text(&disk.name).width(Length::FillPortion(3)),
+                text(&disk.file_system).width(Length::FillPortion(1)),
+                text(gigabytes(disk.total_space)).width(Length::FillPortion(1)),
+                text(gigabytes(disk.available_space)).width(Length::FillPortion(1)),
+                row![
+                    progress_bar(0.0..=100.0, usage).height(Length::Fixed(12.0)),
+                    text(format!("{:.0}%", usage))
+                        .style(usage_color)
+                        .width(Length::Fixed(40.0)),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .width(Length::FillPortion(3)),
+                text(if disk.is_removable { "Yes" } else { "No" }).width(Length::FillPortion(1)),
+                text(format_rate(disk.read_bytes)).width(Length::FillPortion(1)),
+                text(format_rate(disk.written_bytes)).width(Length::FillPortion(1)),
+            ]
+            .spacing(10)
+            .padding(5)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    // Read and write throughput of each mount over the selected window
+    let slots = state.chart_window.samples();
+    let mut charts = column![
+        text("Throughput").size(24),
+        chart_window_buttons(state.chart_window)
+    ]
+    .spacing(10);
+    for disk in disks {
+        let Some(history) = state.disk_manager.history(&disk.mount_point) else {
+            continue;
+        };
+        let read: Vec<f32> = history.last_n(slots).map(|s| s.read_bytes as f32).collect();
+        let written: Vec<f32> = history
+            .last_n(slots)
+            .map(|s| s.written_bytes as f32)
+            .collect();
+        let peak = read.iter().chain(&written).copied().fold(1.0, f32::max);
+        charts = charts.push(
+            column![
+                text(format!(
+                    "{}: read {} / write {}",
+                    disk.mount_point.display(),
+                    format_rate(disk.read_bytes),
+                    format_rate(disk.written_bytes)
+                ))
+                .size(16),
+                LineChart::new(slots)
+                    .series(read, chart::READ_COLOR)
+                    .series(written, chart::WRITE_COLOR)
+                    .max(peak)
+                    .view(Length::Fill, Length::Fixed(80.0)),
+            ]
+            .spacing(4),
+        );
+    }
+
+    let content = column![text("Disks").size(24), table, charts]
+        .spacing(10)
+        .padding(10);
+    scrollable(content).height(Length::Fill).into()
+}
+
+fn view_alerts<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let engine = &state.alert_engine;
+
+    let header = row![
+        text("On").width(Length::Fixed(40.0)),
+        text("Rule").width(Length::FillPortion(2)),
+        text("Condition").width(Length::FillPortion(4)),
+        text("Actions").width(Length::FillPortion(3)),
+        text("For").width(Length::FillPortion(1)),
+        text("Now").width(Length::FillPortion(1)),
+        text("Status").width(Length::FillPortion(2)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let mut rules = column![header].spacing(2);
+    for (index, rule) in engine.rules().iter().enumerate() {
+        let (status, color) = match engine.status(index) {
+            RuleStatus::Ok => (String::from("OK"), Color::from_rgb(0.0, 0.6, 0.0)),
+            RuleStatus::Pending => (String::from("Pending"), Color::from_rgb(0.9, 0.6, 0.0)),
+            RuleStatus::Firing => (String::from("FIRING"), Color::from_rgb(0.8, 0.0, 0.0)),
+            RuleStatus::Disabled => (String::from("Disabled"), Color::from_rgb(0.5, 0.5, 0.5)),
+            RuleStatus::Invalid => (
+                format!("Invalid: {}", engine.error(index).unwrap_or_default()),
+                Color::from_rgb(0.8, 0.0, 0.0),
+            ),
+        };
+        let actions = if rule.actions.is_empty() {
+            String::from("Notify only")
+        } else {
+            let actions: Vec<String> = rule.actions.iter().map(ToString::to_string).collect();
+            let dry_run = if rule.dry_run { " (dry run)" } else { "" };
+            format!("{}{}", actions.join(", "), dry_run)
+        };
+        let current = engine.last_value(index).map_or_else(
+            || String::from("-"),
+            |value| format!("{:.1}{}", value, rule.condition.unit()),
+        );
+        rules = rules.push(
+            row![
+                checkbox("", rule.enabled)
+                    .on_toggle(move |_| Message::ToggleAlertRule(index))
+                    .width(Length::Fixed(40.0)),
+                text(&rule.name).width(Length::FillPortion(2)),
+                text(rule.condition.to_string()).width(Length::FillPortion(4)),
+                text(actions).width(Length::FillPortion(3)),
+                text(format_duration(rule.duration_secs)).width(Length::FillPortion(1)),
+                text(current).width(Length::FillPortion(1)),
+                text(status).style(color).width(Length::FillPortion(2)),
+            ]
+            .spacing(10)
+            .padding(5)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    // Newest alerts first
+    let mut history = column![].spacing(2);
+    for event in engine.history().iter().rev() {
+        let color = match event.kind {
+            AlertKind::Fired => Color::from_rgb(0.8, 0.0, 0.0),
+            AlertKind::Resolved => Color::from_rgb(0.0, 0.6, 0.0),
+            AlertKind::Action => Color::from_rgb(0.2, 0.4, 0.9),
+        };
+        history = history.push(
+            row![
+                text(format_unix_time(event.timestamp)).width(Length::Fixed(200.0)),
+                text(event.kind.to_string())
+                    .style(color)
+                    .width(Length::Fixed(90.0)),
+                text(&event.message),
+            ]
+            .spacing(10)
+            .padding([0, 5]),
+        );
+    }
+    if engine.history().is_empty() {
+        history = history.push(text("No alerts yet").style(Color::from_rgb(0.5, 0.5, 0.5)));
+    }
+
+    column![
+        text("Alert Rules").size(24),
+        text("Rules are read from the config file; thresholds, durations, hysteresis and cooldowns are edited there.")
+            .size(12),
+        rules,
+        row![
+            text("History").size(24),
+            Space::with_width(Length::Fill),
+            button("Clear History")
+                .on_press(Message::ClearAlertHistory)
+                .style(iced::theme::Button::Secondary),
+        ]
+        .align_items(Alignment::Center),
+        scrollable(history).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+fn view_events<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let kind_button = |label, kind: Option<LifecycleKind>| {
+        button(label)
+            .on_press(Message::EventKindSelected(kind))
+            .style(if state.event_kind == kind {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            })
+    };
+    let controls = row![
+        text_input(
+            "Filter events, e.g. nginx or name:worker",
+            state.event_filter()
+        )
+        .on_input(Message::EventFilterChanged)
+        .padding(10),
+        kind_button("All", None),
+        kind_button("Started", Some(LifecycleKind::Started)),
+        kind_button("Exited", Some(LifecycleKind::Exited)),
+        button("Clear")
+            .on_press(Message::ClearEvents)
+            .style(iced::theme::Button::Secondary),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let header = row![
+        text("Time").width(Length::Fixed(200.0)),
+        text("Event").width(Length::Fixed(80.0)),
+        text("PID").width(Length::Fixed(70.0)),
+        text("PPID").width(Length::Fixed(70.0)),
+        text("Name").width(Length::FillPortion(2)),
+        text("Runtime").width(Length::FillPortion(1)),
+        text("CPU %").width(Length::FillPortion(1)),
+        text("Memory").width(Length::FillPortion(1)),
+        text("Command").width(Length::FillPortion(4)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let events = state.filtered_events();
+    let mut list = column![].spacing(2);
+    for event in &events {
+        let process = &event.process;
+        let color = match event.kind {
+            LifecycleKind::Started => Color::from_rgb(0.0, 0.6, 0.0),
+            LifecycleKind::Exited => Color::from_rgb(0.8, 0.0, 0.0),
+        };
+        list = list.push(
+            row![
+                text(format_unix_time(event.timestamp)).width(Length::Fixed(200.0)),
+                text(event.kind.to_string())
+                    .style(color)
+                    .width(Length::Fixed(80.0)),
+                text(process.pid.as_u32()).width(Length::Fixed(70.0)),
+                text(
+                    process
+                        .parent_pid
+                        .map_or_else(|| "N/A".to_string(), |pid| pid.as_u32().to_string())
+                )
+                .width(Length::Fixed(70.0)),
+                text(process.name.to_string_lossy()).width(Length::FillPortion(2)),
+                text(format_duration(process.run_time)).width(Length::FillPortion(1)),
+                text(format!(
+                    "{:.2}",
+                    process.cpu_usage / state.system_manager.cpu_cores.max(1) as f32
+                ))
+                .width(Length::FillPortion(1)),
+                text(format_megabytes(process.memory_usage)).width(Length::FillPortion(1)),
+                text(&process.command_line).width(Length::FillPortion(4)),
+            ]
+            .spacing(10)
+            .padding(5),
+        );
+    }
+
+    let summary = text(format!(
+        "Showing {} of {} events since startup",
+        events.len(),
+        state.event_feed.events().len()
+    ))
+    .size(12);
+
+    column![
+        controls,
+        summary,
+        header,
+        scrollable(list).height(Length::Fill)
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+fn view_watchdog<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let watchdog = &state.watchdog;
+
+    let add_form = row![
+        text_input(
+            if state.watch_by_command_line {
+                "Text in the command line"
+            } else {
+                "Process name"
+            },
+            &state.watch_pattern
+        )
+        .on_input(Message::WatchPatternChanged)
+        .padding(10)
+        .width(Length::FillPortion(2)),
+        checkbox("Match command line", state.watch_by_command_line)
+            .on_toggle(|_| Message::ToggleWatchByCommandLine),
+        text_input("Restart command", &state.watch_command)
+            .on_input(Message::WatchCommandChanged)
+            .on_submit(Message::AddWatch)
+            .padding(10)
+            .width(Length::FillPortion(3)),
+        button("Add")
+            .on_press(Message::AddWatch)
+            .style(iced::theme::Button::Positive),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let header = row![
+        text("On").width(Length::Fixed(40.0)),
+        text("Process").width(Length::FillPortion(3)),
+        text("Restart command").width(Length::FillPortion(4)),
+        text("Restarts").width(Length::FillPortion(1)),
+        text("Status").width(Length::FillPortion(1)),
+        Space::with_width(Length::Fixed(150.0)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let mut entries = column![header].spacing(2);
+    for (index, entry) in watchdog.entries().iter().enumerate() {
+        let status = watchdog.status(index);
+        let color = match status {
+            WatchStatus::Running => Color::from_rgb(0.0, 0.6, 0.0),
+            WatchStatus::Restarting => Color::from_rgb(0.9, 0.6, 0.0),
+            WatchStatus::GaveUp => Color::from_rgb(0.8, 0.0, 0.0),
+            WatchStatus::Waiting | WatchStatus::Disabled => Color::from_rgb(0.5, 0.5, 0.5),
+        };
+        entries = entries.push(
+            row![
+                checkbox("", entry.enabled)
+                    .on_toggle(move |_| Message::ToggleWatch(index))
+                    .width(Length::Fixed(40.0)),
+                text(entry.target.to_string()).width(Length::FillPortion(3)),
+                text(&entry.restart_command).width(Length::FillPortion(4)),
+                text(format!(
+                    "{} / {}",
+                    watchdog.restarts(index),
+                    entry.max_restarts
+                ))
+                .width(Length::FillPortion(1)),
+                text(status.to_string())
+                    .style(color)
+                    .width(Length::FillPortion(1)),
+                button("Reset")
+                    .on_press(Message::ResetWatch(index))
+                    .style(iced::theme::Button::Secondary),
+                button("Remove")
+                    .on_press(Message::RemoveWatch(index))
+                    .style(iced::theme::Button::Destructive),
+            ]
+            .spacing(10)
+            .padding(5)
+            .align_items(Alignment::Center),
+        );
+    }
+    if watchdog.entries().is_empty() {
+        entries = entries.push(
+            text("Nothing watched yet. Select a process and press Watch, or add one above.")
+                .style(Color::from_rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    column![
+        text("Watchdog").size(24),
+        text("Watched processes are restarted when they exit, waiting longer after each restart until the budget runs out. Backoff and budget can be tuned in the config file.")
+            .size(12),
+        add_form,
+        scrollable(entries).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+fn view_network<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    let interfaces = state.network_manager.interfaces();
+    if interfaces.is_empty() {
+        return container(text("No network interfaces found"))
+            .padding(10)
+            .into();
+    }
+
+    let header = row![
+        text("Interface").width(Length::FillPortion(2)),
+        text("MAC").width(Length::FillPortion(2)),
+        text("Addresses").width(Length::FillPortion(3)),
+        text("Rx").width(Length::FillPortion(1)),
+        text("Tx").width(Length::FillPortion(1)),
+        text("Rx Packets").width(Length::FillPortion(1)),
+        text("Tx Packets").width(Length::FillPortion(1)),
+        text("Errors (Rx/Tx)").width(Length::FillPortion(1)),
+        text("Rx Rate").width(Length::FillPortion(1)),
+        text("Tx Rate").width(Length::FillPortion(1)),
+    ]
+    .spacing(10)
+    .padding(5);
+
+    let mut table = column![header].spacing(2);
+    for interface in interfaces {
+        let errors = interface.total_errors_received + interface.total_errors_transmitted;
+        let errors_color = if errors > 0 {
+            Color::from_rgb(0.8, 0.0, 0.0)
+        } else {
+            Color::from_rgb(0.7, 0.7, 0.7)
+        };
+        let addresses = if interface.ip_addresses.is_empty() {
+            String::from("-")
+        } else {
+            interface.ip_addresses.join("\n")
+        };
+        table = table.push(
+            row![
+                text(&interface.name).width(Length::FillPortion(2)),
+                text(&interface.mac_address).width(Length::FillPortion(2)),
+                text(addresses).width(Length::FillPortion(3)),
+                text(format_megabytes(interface.total_received)).width(Length::FillPortion(1)),
+                text(format_megabytes(interface.total_transmitted)).width(Length::FillPortion(1)),
+                text(interface.total_packets_received).width(Length::FillPortion(1)),
+                text(interface.total_packets_transmitted).width(Length::FillPortion(1)),
+                text(format!(
+                    "{} / {}",
+                    interface.total_errors_received, interface.total_errors_transmitted
+                ))
+                .style(errors_color)
+                .width(Length::FillPortion(1)),
+                text(format_rate(interface.received)).width(Length::FillPortion(1)),
+                text(format_rate(interface.transmitted)).width(Length::FillPortion(1)),
+            ]
+            .spacing(10)
+            .padding(5)
+            .align_items(Alignment::Center),
+        );
+    }
+
+    // Received and transmitted traffic of each interface over the selected window
+    let slots = state.chart_window.samples();
+    let mut charts = column![
+        text("Traffic").size(24),
+        chart_window_buttons(state.chart_window)
+    ]
+    .spacing(10);
+    for interface in interfaces {
+        let Some(history) = state.network_manager.history(&interface.name) else {
+            continue;
+        };
+        let received: Vec<f32> = history.last_n(slots).map(|s| s.received as f32).collect();
+        let transmitted: Vec<f32> = history
+            .last_n(slots)
+            .map(|s| s.transmitted as f32)
+            .collect();
+        let peak = received
+            .iter()
+            .chain(&transmitted)
+            .copied()
+            .fold(1.0, f32::max);
+        charts = charts.push(
+            column![
+                text(format!(
+                    "{}: rx {} / tx {}",
+                    interface.name,
+                    format_rate(interface.received),
+                    format_rate(interface.transmitted)
+                ))
+                .size(16),
+                LineChart::new(slots)
+                    .series(received, chart::READ_COLOR)
+                    .series(transmitted, chart::WRITE_COLOR)
+                    .max(peak)
+                    .view(Length::Fill, Length::Fixed(80.0)),
+            ]
+            .spacing(4),
+        );
+    }
+
+    let content = column![text("Network").size(24), table, charts]
+        .spacing(10)
+        .padding(10);
+    scrollable(content).height(Length::Fill).into()
+}
+
+fn view_column_chooser<'a>(state: &'a TarnerMonitor) -> Element<'a, Message> {
+    // Shown columns first in their display order, then the hidden ones
+    let hidden = ProcessColumn::ALL
+        .into_iter()
+        .filter(|column| !state.columns.iter().any(|c| c.column == *column));
+    let order = state.columns.iter().map(|c| c.column).chain(hidden);
+
+    let mut list = Column::new().spacing(2);
+    for column in order {
+        let layout = state.columns.iter().find(|c| c.column == column);
+        let mut line = row![
+            checkbox(column.label(), layout.is_some())
+                .on_toggle(move |_| Message::ToggleColumn(column))
+                .width(Length::Fixed(180.0))
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        if let Some(layout) = layout {
+            line = line.push(
+                row![
+                    button("◀").on_press(Message::MoveColumn(column, -1)),
+                    button("▶").on_press(Message::MoveColumn(column, 1)),
+                    button("-").on_press(Message::ResizeColumn(column, -columns::WIDTH_STEP)),
+                    text(format!("{:.0}px", layout.width)).width(Length::Fixed(50.0)),
+                    button("+").on_press(Message::ResizeColumn(column, columns::WIDTH_STEP)),
+                ]
+                .spacing(5)
+                .align_items(Alignment::Center),
+            );
+        }
+        list = list.push(line);
+    }
+
+    column![
+        text("Process Table Columns").size(20),
+        container(scrollable(list).height(Length::Fixed(200.0))).padding([0, 20]),
+    ]
+    .spacing(10)
+    .into()
+}
+
 fn view_settings<'a>(state: &'a TarnerMonitor, _theme: Theme) -> Element<'a, Message> {
     let theme_text = match state.theme {
         AppTheme::Light => "Dark Mode",
@@ -313,6 +1470,33 @@ fn view_settings<'a>(state: &'a TarnerMonitor, _theme: Theme) -> Element<'a, Mes
         .on_press(Message::ExportToCsv)
         .style(iced::theme::Button::Positive);
 
+    let grace_period = row![
+        text("SIGKILL grace period:"),
+        button("-").on_press(Message::GracePeriodChanged(
+            state.grace_period_secs.saturating_sub(1)
+        )),
+        text(format!("{}s", state.grace_period_secs)),
+        button("+").on_press(Message::GracePeriodChanged(state.grace_period_secs + 1)),
+    ]
+    .spacing(10)
+    .padding([0, 20])
+    .align_items(Alignment::Center);
+
+    let retention_minutes = state.history_retention_secs / 60;
+    let history_retention = row![
+        text("Process history retention:"),
+        button("-").on_press(Message::HistoryRetentionChanged(
+            retention_minutes.saturating_sub(1) * 60
+        )),
+        text(format!("{} min", retention_minutes)),
+        button("+").on_press(Message::HistoryRetentionChanged(
+            (retention_minutes + 1) * 60
+        )),
+    ]
+    .spacing(10)
+    .padding([0, 20])
+    .align_items(Alignment::Center);
+
     let reload_logs_button = button("Reload Logs").on_press(Message::LoadLogs);
 
     let logs_title = row![text("Event Logs").size(20), reload_logs_button,]
@@ -347,6 +1531,9 @@ fn view_settings<'a>(state: &'a TarnerMonitor, _theme: Theme) -> Element<'a, Mes
         row![theme_toggle.padding(20), export_csv.padding(20),]
             .spacing(10)
             .padding(20),
+        grace_period,
+        history_retention,
+        view_column_chooser(state),
         logs_title,
         logs_container,
     ]
@@ -0,0 +1,260 @@
+use crate::process::ProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How a watched process is recognised in the process list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatchTarget {
+    /// Process name, exactly
+    Name(String),
+    /// Text contained in the full command line
+    CommandLine(String),
+}
+
+impl WatchTarget {
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        match self {
+            WatchTarget::Name(name) => process.name.to_string_lossy() == name.as_str(),
+            WatchTarget::CommandLine(text) => process.command_line.contains(text.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for WatchTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchTarget::Name(name) => write!(f, "name = {}", name),
+            WatchTarget::CommandLine(text) => write!(f, "command line contains {}", text),
+        }
+    }
+}
+
+/// A pinned process and the command that brings it back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub target: WatchTarget,
+    pub restart_command: String,
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Restarts allowed before the watchdog gives up
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+    /// Wait before the first restart; doubles with every further restart
+    #[serde(default = "default_backoff_secs")]
+    pub backoff_secs: u64,
+    /// Longest wait between restarts
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// Uptime after which the restart budget is refilled
+    #[serde(default = "default_stable_secs")]
+    pub stable_secs: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+
+fn default_backoff_secs() -> u64 {
+    2
+}
+
+fn default_max_backoff_secs() -> u64 {
+    60
+}
+
+fn default_stable_secs() -> u64 {
+    60
+}
+
+impl WatchEntry {
+    pub fn new(target: WatchTarget, restart_command: String) -> Self {
+        WatchEntry {
+            target,
+            restart_command,
+            working_dir: None,
+            enabled: true,
+            max_restarts: default_max_restarts(),
+            backoff_secs: default_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+            stable_secs: default_stable_secs(),
+        }
+    }
+
+    /// Wait before restart number `attempt` (counting from zero)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let secs = self
+            .backoff_secs
+            .saturating_mul(1u64 << attempt.min(32))
+            .min(self.max_backoff_secs.max(self.backoff_secs));
+        Duration::from_secs(secs)
+    }
+}
+
+/// Where a watched process is in its restart cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchStatus {
+    /// Not seen yet; the watchdog arms once the process has been running
+    Waiting,
+    Running,
+    /// Gone, with a restart scheduled
+    Restarting,
+    /// Restart budget used up; reset to try again
+    GaveUp,
+    Disabled,
+}
+
+impl fmt::Display for WatchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchStatus::Waiting => write!(f, "Waiting"),
+            WatchStatus::Running => write!(f, "Running"),
+            WatchStatus::Restarting => write!(f, "Restarting"),
+            WatchStatus::GaveUp => write!(f, "Gave up"),
+            WatchStatus::Disabled => write!(f, "Disabled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct WatchState {
+    /// Seen running, or restarted by us, since the entry was added or reset
+    armed: bool,
+    up_since: Option<Instant>,
+    next_restart: Option<Instant>,
+    restarts: u32,
+    gave_up: bool,
+}
+
+/// Something the caller has to act on or report after a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// The process is gone; a restart is due after the delay
+    Lost { index: usize, retry_in: Duration },
+    /// Run the entry's restart command now; `attempt` counts from one
+    Restart { index: usize, attempt: u32 },
+    /// The restart budget is spent
+    GaveUp { index: usize },
+}
+
+/// Keeps pinned processes running by restarting them with backoff
+pub struct Watchdog {
+    entries: Vec<WatchEntry>,
+    states: Vec<WatchState>,
+}
+
+impl Watchdog {
+    pub fn new(entries: Vec<WatchEntry>) -> Self {
+        Watchdog {
+            states: vec![WatchState::default(); entries.len()],
+            entries,
+        }
+    }
+
+    pub fn entries(&self) -> &[WatchEntry] {
+        &self.entries
+    }
+
+    pub fn add(&mut self, entry: WatchEntry) {
+        self.entries.push(entry);
+        self.states.push(WatchState::default());
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<WatchEntry> {
+        if index >= self.entries.len() {
+            return None;
+        }
+        self.states.remove(index);
+        Some(self.entries.remove(index))
+    }
+
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.enabled = enabled;
+            self.states[index] = WatchState::default();
+        }
+    }
+
+    /// Refill the restart budget and forget any scheduled restart
+    pub fn reset(&mut self, index: usize) {
+        if let Some(state) = self.states.get_mut(index) {
+            *state = WatchState {
+                armed: state.armed,
+                ..WatchState::default()
+            };
+        }
+    }
+
+    pub fn restarts(&self, index: usize) -> u32 {
+        self.states.get(index).map_or(0, |s| s.restarts)
+    }
+
+    pub fn status(&self, index: usize) -> WatchStatus {
+        match (self.entries.get(index), self.states.get(index)) {
+            (Some(entry), _) if !entry.enabled => WatchStatus::Disabled,
+            (_, Some(state)) if state.gave_up => WatchStatus::GaveUp,
+            (_, Some(state)) if state.up_since.is_some() => WatchStatus::Running,
+            (_, Some(state)) if state.armed => WatchStatus::Restarting,
+            _ => WatchStatus::Waiting,
+        }
+    }
+
+    /// Look for every enabled entry in `processes` at `now`.
+    ///
+    /// This scans the current list rather than following exit events: an entry
+    /// may match several processes and only counts as gone once none is left,
+    /// and it arms on seeing a match, which no event reports for processes that
+    /// were already running when it was added.
+    pub fn check(&mut self, processes: &[ProcessInfo], now: Instant) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        for (index, (entry, state)) in self.entries.iter().zip(self.states.iter_mut()).enumerate() {
+            if !entry.enabled {
+                continue;
+            }
+            if processes.iter().any(|p| entry.target.matches(p)) {
+                let up_since = *state.up_since.get_or_insert(now);
+                state.armed = true;
+                state.next_restart = None;
+                if now.duration_since(up_since) >= Duration::from_secs(entry.stable_secs) {
+                    state.restarts = 0;
+                    state.gave_up = false;
+                }
+                continue;
+            }
+
+            state.up_since = None;
+            if !state.armed || state.gave_up {
+                continue;
+            }
+            if state.restarts >= entry.max_restarts {
+                state.gave_up = true;
+                events.push(WatchEvent::GaveUp { index });
+                continue;
+            }
+            match state.next_restart {
+                None => {
+                    let retry_in = entry.backoff(state.restarts);
+                    state.next_restart = Some(now + retry_in);
+                    events.push(WatchEvent::Lost { index, retry_in });
+                }
+                Some(due) if now >= due => {
+                    state.restarts += 1;
+                    state.next_restart = None;
+                    events.push(WatchEvent::Restart {
+                        index,
+                        attempt: state.restarts,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        events
+    }
+}
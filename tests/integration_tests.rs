@@ -1,7 +1,13 @@
+// The baseline tests predate these lints and are kept as written
+#![allow(clippy::manual_abs_diff, clippy::len_zero)]
+
 use std::thread;
 use std::time::Duration;
+use sysinfo::Pid;
+use tarner_monitor::columns::ProcessColumn;
+use tarner_monitor::sort::{SortField, SortKey};
 use tarner_monitor::state::{Tab, TarnerMonitor};
-use tarner_monitor::system::SystemManager;
+use tarner_monitor::system::{KillOutcome, KillSignal, SystemManager};
 
 // test 1: complete monitoring cycle
 #[test]
@@ -44,14 +50,14 @@ fn test_process_search_and_filter() {
     monitor.refresh_processes();
     let total_processes = monitor.processes.len();
     println!("Total processes: {}", total_processes);
-    monitor.search_str = String::new();
+    monitor.set_search(String::new());
     let all_filtered = monitor.get_filtered();
     assert_eq!(all_filtered.len(), total_processes);
     println!("Empty search returns all {} processes", all_filtered.len());
     if let Some(first_process) = monitor.processes.first() {
         let process_name = first_process.name.to_string_lossy().to_string();
         let search_term = process_name.chars().take(3).collect::<String>();
-        monitor.search_str = search_term.clone();
+        monitor.set_search(search_term.clone());
         let filtered = monitor.get_filtered();
         println!(
             "Searching for '{}': found {} processes",
@@ -87,7 +93,7 @@ fn test_sorting_functionality() {
         return;
     }
     // test CPU sorting
-    monitor.current_sort = tarner_monitor::state::SortBy::CpuDesc;
+    monitor.current_sort = vec![SortKey::descending(SortField::Column(ProcessColumn::Cpu))];
     monitor.apply_sort();
     let first_cpu = monitor.processes[0].cpu_usage;
     let last_cpu = monitor.processes[monitor.processes.len() - 1].cpu_usage;
@@ -99,7 +105,9 @@ fn test_sorting_functionality() {
     );
     println!("CPU sorting works: {} >= {}", first_cpu, last_cpu);
     // test Memory sorting
-    monitor.current_sort = tarner_monitor::state::SortBy::MemDesc;
+    monitor.current_sort = vec![SortKey::descending(SortField::Column(
+        ProcessColumn::Memory,
+    ))];
     monitor.apply_sort();
     let first_mem = monitor.processes[0].memory_usage;
     let last_mem = monitor.processes[monitor.processes.len() - 1].memory_usage;
@@ -111,7 +119,7 @@ fn test_sorting_functionality() {
     );
     println!("Memory sorting works: {} >= {}", first_mem, last_mem);
     // test Alphabetical sorting
-    monitor.current_sort = tarner_monitor::state::SortBy::AlphaAsc;
+    monitor.current_sort = vec![SortKey::ascending(SortField::Name)];
     monitor.apply_sort();
     let first_name = monitor.processes[0].name.to_string_lossy();
     let second_name = monitor.processes[1].name.to_string_lossy();
@@ -212,7 +220,7 @@ fn test_system_information_retrieval() {
     println!("Cores: {}", system_manager.cpu_cores);
     // verify memory info
     assert!(system_manager.total_memory > 0);
-    let used_memory = system_manager.system.used_memory();
+    let used_memory = system_manager.used_memory();
     assert!(used_memory > 0);
     assert!(used_memory <= system_manager.total_memory);
     println!(
@@ -222,3 +230,60 @@ fn test_system_information_retrieval() {
     println!("Used Memory: {} MB", used_memory / 1024 / 1024);
     println!("System information retrieval test passed!");
 }
+
+// test 7: extended details of the test process itself
+#[test]
+fn test_own_process_details() {
+    println!("Testing extended process details...");
+    let system_manager = SystemManager::new();
+    let own_pid = std::process::id();
+    let processes = system_manager.get_processes();
+    let own = processes
+        .iter()
+        .find(|p| p.pid.as_u32() == own_pid)
+        .expect("Test process not listed");
+
+    println!("Command line: {}", own.command_line);
+    println!("Executable: {:?}", own.exe);
+    println!("User: {}", own.user_label());
+    assert!(!own.command_line.is_empty());
+    assert!(own.exe.is_some());
+    assert!(own.cwd.is_some());
+    assert!(own.start_time > 0);
+    assert!(own.thread_count >= 1);
+    #[cfg(unix)]
+    assert!(own.uid.is_some());
+    println!("Extended process details test passed!");
+}
+
+// test 8: signalling a real child process, then the same PID once it has been reaped
+#[cfg(unix)]
+#[test]
+fn test_kill_real_process() {
+    println!("Testing kill outcomes against a live process...");
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .expect("Failed to start sleep");
+    let pid = Pid::from_u32(child.id());
+    let mut system_manager = SystemManager::new();
+    system_manager.refresh();
+
+    assert_eq!(
+        system_manager.kill_process(pid, KillSignal::Term),
+        KillOutcome::Sent
+    );
+    child.wait().expect("Failed to reap sleep");
+    // Still listed until the next refresh, so the outcome comes from errno
+    assert_eq!(
+        system_manager.kill_process(pid, KillSignal::Term),
+        KillOutcome::NoSuchProcess
+    );
+    // PID 0 would address the monitor's own process group
+    assert!(matches!(
+        system_manager.kill_process(Pid::from_u32(0), KillSignal::Term),
+        KillOutcome::Failed(_)
+    ));
+    assert!(system_manager.renice_process(Pid::from_u32(0), 5).is_err());
+    println!("Kill outcome test passed!");
+}
@@ -1,8 +1,36 @@
+use clap::Parser;
+use iced::Application;
+use iced::keyboard::Modifiers;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use sysinfo::{DiskUsage, Pid, ProcessStatus};
+use tarner_monitor::alerts::{
+    self, AlertAction, AlertCondition, AlertEngine, AlertInputs, AlertKind, AlertRule, RuleStatus,
+};
+use tarner_monitor::cli::{self, Cli, CliCommand};
+use tarner_monitor::columns::{self, ProcessColumn};
+use tarner_monitor::disks::{DiskInfo, DiskIoSample, DiskManager};
+use tarner_monitor::events::LifecycleKind;
+use tarner_monitor::history::{RingBuffer, Trend};
+use tarner_monitor::network::{NetworkInfo, NetworkManager, NetworkSample};
+use tarner_monitor::presets;
 use tarner_monitor::process::ProcessInfo;
-use tarner_monitor::state::{AppTheme, SortBy, Tab, TarnerMonitor};
-use tarner_monitor::system::SystemManager;
+use tarner_monitor::query::{Query, QueryContext, SearchOptions};
+use tarner_monitor::sensors::{SensorInfo, SensorLevel};
+use tarner_monitor::sort::{self, SortField, SortKey};
+use tarner_monitor::source::ScriptedSource;
+use tarner_monitor::state::{
+    AppTheme, ChartWindow, Effect, KillTarget, Message, TOAST_DURATION, Tab, TarnerMonitor,
+    ToastType, write_csv,
+};
+use tarner_monitor::system::{self, KillOutcome, KillSignal, SystemManager};
+use tarner_monitor::tree;
+use tarner_monitor::tui::Tui;
+use tarner_monitor::watchdog::{WatchEntry, WatchEvent, WatchStatus, WatchTarget, Watchdog};
 
 // test 1: processInfo creation
 #[test]
@@ -117,7 +145,7 @@ fn test_total_memory_validation() {
 #[test]
 fn test_process_filtering_empty_search() {
     let mut monitor = TarnerMonitor::new();
-    monitor.search_str = String::new();
+    monitor.set_search(String::new());
     let filtered = monitor.get_filtered();
     // if empty, should return all processes
     assert_eq!(filtered.len(), monitor.processes.len());
@@ -164,7 +192,7 @@ fn test_process_filtering_with_search() {
         ),
     ];
     // search for "chrome"
-    monitor.search_str = String::from("chrome");
+    monitor.set_search(String::from("chrome"));
     let filtered = monitor.get_filtered();
     assert_eq!(filtered.len(), 1);
     assert_eq!(filtered[0].name.to_string_lossy(), "chrome");
@@ -186,7 +214,7 @@ fn test_case_insensitive_search() {
         DiskUsage::default(),
     )];
     // search with lowercase should find uppercase
-    monitor.search_str = String::from("chrome");
+    monitor.set_search(String::from("chrome"));
     let filtered = monitor.get_filtered();
     assert_eq!(filtered.len(), 1);
 }
@@ -230,7 +258,7 @@ fn test_sort_alphabetically_ascending() {
             DiskUsage::default(),
         ),
     ];
-    monitor.current_sort = SortBy::AlphaAsc;
+    monitor.current_sort = vec![SortKey::ascending(SortField::Name)];
     monitor.apply_sort();
     assert_eq!(monitor.processes[0].name.to_string_lossy(), "apple");
     assert_eq!(monitor.processes[1].name.to_string_lossy(), "middle");
@@ -276,7 +304,7 @@ fn test_sort_by_cpu_descending() {
             DiskUsage::default(),
         ),
     ];
-    monitor.current_sort = SortBy::CpuDesc;
+    monitor.current_sort = vec![SortKey::descending(SortField::Column(ProcessColumn::Cpu))];
     monitor.apply_sort();
     assert_eq!(monitor.processes[0].cpu_usage, 50.0);
     assert_eq!(monitor.processes[1].cpu_usage, 25.0);
@@ -322,7 +350,9 @@ fn test_sort_by_memory_descending() {
             DiskUsage::default(),
         ),
     ];
-    monitor.current_sort = SortBy::MemDesc;
+    monitor.current_sort = vec![SortKey::descending(SortField::Column(
+        ProcessColumn::Memory,
+    ))];
     monitor.apply_sort();
     assert_eq!(monitor.processes[0].memory_usage, 8192);
     assert_eq!(monitor.processes[1].memory_usage, 4096);
@@ -394,3 +424,2159 @@ fn test_process_selection_persistence_after_refresh() {
         println!("Selected process was correctly cleared (process terminated)");
     }
 }
+
+fn scripted_process(name: &str, pid: u32, parent: Option<u32>, cpu: f32, mem: u64) -> ProcessInfo {
+    ProcessInfo::new(
+        OsString::from(name),
+        parent.map(Pid::from_u32),
+        Pid::from_u32(pid),
+        cpu,
+        mem,
+        100,
+        ProcessStatus::Run,
+        100,
+        DiskUsage::default(),
+    )
+}
+
+// test 16: scripted source feeds exact system figures and processes
+#[test]
+fn test_scripted_source_system_figures() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("zeta", 10, None, 5.0, 100),
+        scripted_process("alpha", 11, None, 1.0, 300),
+    ])
+    .with_cpu_cores(8)
+    .with_total_memory(4096)
+    .with_used_memory(1024);
+    let monitor = TarnerMonitor::with_source(source);
+    assert_eq!(monitor.system_manager.cpu_cores, 8);
+    assert_eq!(monitor.system_manager.total_memory, 4096);
+    assert_eq!(monitor.system_manager.used_memory(), 1024);
+    // default sort is alphabetical ascending
+    assert_eq!(monitor.processes.len(), 2);
+    assert_eq!(monitor.processes[0].name.to_string_lossy(), "alpha");
+    assert_eq!(monitor.processes[1].name.to_string_lossy(), "zeta");
+}
+
+// test 17: selection follows the PID across refreshes and clears on exit
+#[test]
+fn test_selection_after_scripted_refresh() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("worker", 20, Some(1), 10.0, 1000),
+        scripted_process("daemon", 21, Some(1), 2.0, 2000),
+    ])
+    .then(vec![
+        scripted_process("worker", 20, Some(1), 40.0, 1500),
+        scripted_process("daemon", 21, Some(1), 2.0, 2000),
+    ])
+    .then(vec![scripted_process("daemon", 21, Some(1), 2.0, 2000)]);
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(20)));
+    monitor.refresh_processes();
+    let selected = monitor
+        .selected_process
+        .as_ref()
+        .expect("worker still running");
+    assert_eq!(selected.cpu_usage, 40.0);
+    assert_eq!(selected.memory_usage, 1500);
+
+    monitor.refresh_processes();
+    assert!(monitor.selected_process.is_none());
+}
+
+// test 18: ending the parent is an explicit separate action
+#[test]
+fn test_confirm_kill_parent_with_scripted_source() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("shell", 30, None, 0.0, 100),
+        scripted_process("child", 31, Some(30), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(31)));
+    let _ = monitor.update(Message::RequestKillParent);
+    assert!(monitor.kill_confirm);
+    assert_eq!(monitor.kill_target, KillTarget::Parent);
+    let _ = monitor.update(Message::ConfirmKill);
+
+    assert!(!monitor.kill_confirm);
+    assert_eq!(
+        *kill_log.lock().unwrap(),
+        vec![(Pid::from_u32(30), KillSignal::Term)]
+    );
+    assert!(matches!(monitor.toast, Some((_, ToastType::Success))));
+}
+
+// test 19: end task sends the chosen signal to the selected process itself
+#[test]
+fn test_confirm_kill_sends_selected_signal() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("shell", 30, None, 0.0, 100),
+        scripted_process("child", 31, Some(30), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(31)));
+    let _ = monitor.update(Message::RequestKill);
+    assert_eq!(monitor.kill_target, KillTarget::Process);
+    let _ = monitor.update(Message::SignalSelected(KillSignal::Kill));
+    let _ = monitor.update(Message::ConfirmKill);
+
+    assert_eq!(
+        *kill_log.lock().unwrap(),
+        vec![(Pid::from_u32(31), KillSignal::Kill)]
+    );
+    let (msg, style) = monitor.toast.clone().unwrap();
+    assert_eq!(style, ToastType::Success);
+    assert!(msg.contains("SIGKILL"), "unexpected toast: {}", msg);
+}
+
+// test 20: permission denied and vanished processes are reported distinctly
+#[test]
+fn test_kill_failures_reported_distinctly() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("root_daemon", 40, None, 0.0, 100),
+        scripted_process("orphan", 41, Some(99), 0.0, 100),
+    ])
+    .protect(Pid::from_u32(40));
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(40)));
+    let (outcome, _) = monitor.signal_selected();
+    assert_eq!(outcome, KillOutcome::PermissionDenied);
+
+    // parent 99 is not in the process list
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(41)));
+    monitor.kill_target = KillTarget::Parent;
+    let (outcome, _) = monitor.signal_selected();
+    assert_eq!(outcome, KillOutcome::NoSuchProcess);
+}
+
+// test 21: graceful termination escalates to SIGKILL after the grace period
+#[test]
+fn test_graceful_termination_escalates() {
+    let source = ScriptedSource::new(vec![scripted_process("service", 50, None, 0.0, 100)])
+        .ignore(Pid::from_u32(50), KillSignal::Term);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.grace_period_secs = 5;
+
+    let start = Instant::now();
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(50)));
+    let _ = monitor.update(Message::RequestKill);
+    let (outcome, _) = monitor.terminate_gracefully(start);
+    assert!(outcome.is_sent());
+    assert_eq!(monitor.terminations.len(), 1);
+    assert_eq!(monitor.terminations[0].remaining_secs(start), 5);
+
+    // still inside the grace period: no escalation yet
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(2)));
+    assert_eq!(monitor.terminations.len(), 1);
+    assert_eq!(kill_log.lock().unwrap().len(), 1);
+
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(6)));
+    assert!(monitor.terminations.is_empty());
+    assert_eq!(
+        *kill_log.lock().unwrap(),
+        vec![
+            (Pid::from_u32(50), KillSignal::Term),
+            (Pid::from_u32(50), KillSignal::Kill)
+        ]
+    );
+}
+
+// test 22: a process that exits on SIGTERM or a canceled escalation never gets SIGKILL
+#[test]
+fn test_graceful_termination_exit_and_cancel() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("polite", 60, None, 0.0, 100),
+        scripted_process("stubborn", 61, None, 0.0, 100),
+    ])
+    .ignore(Pid::from_u32(61), KillSignal::Term);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let start = Instant::now();
+    for pid in [60, 61] {
+        let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(pid)));
+        monitor.terminate_gracefully(start);
+    }
+    assert_eq!(monitor.terminations.len(), 2);
+
+    let _ = monitor.update(Message::CancelEscalation(Pid::from_u32(61)));
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(60)));
+
+    assert!(monitor.terminations.is_empty());
+    assert!(
+        !kill_log
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, signal)| *signal == KillSignal::Kill)
+    );
+}
+
+// test 23: subtree ordering puts every child before its parent
+#[test]
+fn test_subtree_bottom_up_order() {
+    let processes = vec![
+        scripted_process("make", 70, Some(1), 0.0, 100),
+        scripted_process("cc", 71, Some(70), 0.0, 100),
+        scripted_process("cc1", 72, Some(71), 0.0, 100),
+        scripted_process("as", 73, Some(71), 0.0, 100),
+        scripted_process("ld", 74, Some(70), 0.0, 100),
+        scripted_process("unrelated", 75, Some(1), 0.0, 100),
+    ];
+    let order = tree::subtree_bottom_up(&processes, Pid::from_u32(70));
+    assert_eq!(order.len(), 5);
+    assert_eq!(*order.last().unwrap(), Pid::from_u32(70));
+    assert!(!order.contains(&Pid::from_u32(75)));
+    let position = |pid: u32| order.iter().position(|p| *p == Pid::from_u32(pid)).unwrap();
+    assert!(position(72) < position(71));
+    assert!(position(73) < position(71));
+    assert!(position(71) < position(70));
+    assert!(position(74) < position(70));
+}
+
+// test 24: ending a tree signals all descendants bottom-up and lists them first
+#[test]
+fn test_kill_process_tree() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("make", 70, Some(1), 0.0, 100),
+        scripted_process("cc", 71, Some(70), 0.0, 100),
+        scripted_process("cc1", 72, Some(71), 0.0, 100),
+        scripted_process("sibling", 80, Some(1), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(70)));
+    let _ = monitor.update(Message::RequestKillTree);
+    let preview: Vec<u32> = monitor
+        .kill_preview()
+        .iter()
+        .map(|p| p.pid.as_u32())
+        .collect();
+    assert_eq!(preview, vec![72, 71, 70]);
+
+    let _ = monitor.update(Message::ConfirmKill);
+    let signaled: Vec<u32> = kill_log
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    assert_eq!(signaled, vec![72, 71, 70]);
+    assert!(matches!(monitor.toast, Some((_, ToastType::Success))));
+}
+
+// test 25: tree view nests rows, aggregates collapsed subtrees and sorts siblings
+#[test]
+fn test_tree_rows_nesting_and_collapse() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("init", 1, None, 1.0, 100),
+        scripted_process("shell", 10, Some(1), 2.0, 200),
+        scripted_process("vim", 11, Some(10), 4.0, 400),
+        scripted_process("cargo", 12, Some(10), 8.0, 800),
+        scripted_process("cron", 20, Some(1), 16.0, 1600),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    let _ = monitor.update(Message::ToggleTreeMode);
+    assert!(monitor.tree_mode);
+
+    let layout: Vec<(u32, usize)> = monitor
+        .tree_rows()
+        .iter()
+        .map(|r| (r.process.pid.as_u32(), r.depth))
+        .collect();
+    // alphabetical among siblings: cron < shell, cargo < vim
+    assert_eq!(layout, vec![(1, 0), (20, 1), (10, 1), (12, 2), (11, 2)]);
+
+    let _ = monitor.update(Message::ToggleExpanded(Pid::from_u32(10)));
+    let rows = monitor.tree_rows();
+    assert_eq!(rows.len(), 3);
+    let shell = rows
+        .iter()
+        .find(|r| r.process.pid == Pid::from_u32(10))
+        .unwrap();
+    assert!(shell.has_children && !shell.expanded);
+    assert_eq!(shell.subtree_cpu, 14.0);
+    assert_eq!(shell.subtree_memory, 1400);
+}
+
+// test 26: tree search keeps the ancestors of matching processes visible
+#[test]
+fn test_tree_search_keeps_ancestors() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("init", 1, None, 0.0, 100),
+        scripted_process("shell", 10, Some(1), 0.0, 100),
+        scripted_process("vim", 11, Some(10), 0.0, 100),
+        scripted_process("cron", 20, Some(1), 0.0, 100),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.tree_mode = true;
+    monitor.set_search(String::from("vim"));
+
+    let layout: Vec<(u32, usize)> = monitor
+        .tree_rows()
+        .iter()
+        .map(|r| (r.process.pid.as_u32(), r.depth))
+        .collect();
+    assert_eq!(layout, vec![(1, 0), (10, 1), (11, 2)]);
+}
+
+// test 27: ctrl toggles rows, shift extends a range, selection survives refresh by PID
+#[test]
+fn test_multi_select_and_refresh() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("a", 1, None, 0.0, 100),
+        scripted_process("b", 2, None, 0.0, 100),
+        scripted_process("c", 3, None, 0.0, 100),
+        scripted_process("d", 4, None, 0.0, 100),
+    ])
+    .then(vec![
+        scripted_process("a", 1, None, 0.0, 100),
+        scripted_process("c", 3, None, 0.0, 100),
+        scripted_process("d", 4, None, 0.0, 100),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    monitor.select_process(Pid::from_u32(1), Modifiers::empty());
+    monitor.select_process(Pid::from_u32(3), Modifiers::SHIFT);
+    let selected: Vec<u32> = monitor.selected_pids.iter().map(|p| p.as_u32()).collect();
+    assert_eq!(selected, vec![1, 2, 3]);
+
+    monitor.select_process(Pid::from_u32(1), Modifiers::CTRL);
+    monitor.select_process(Pid::from_u32(4), Modifiers::CTRL);
+    let selected: Vec<u32> = monitor.selected_pids.iter().map(|p| p.as_u32()).collect();
+    assert_eq!(selected, vec![2, 3, 4]);
+
+    monitor.refresh_processes();
+    let selected: Vec<u32> = monitor.selected_pids.iter().map(|p| p.as_u32()).collect();
+    assert_eq!(selected, vec![3, 4]);
+}
+
+// test 28: bulk kill and renice act on the whole selection
+#[test]
+fn test_bulk_kill_and_renice() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("worker", 11, None, 0.0, 100),
+        scripted_process("worker", 12, None, 0.0, 100),
+        scripted_process("worker", 13, None, 0.0, 100),
+        scripted_process("other", 14, None, 0.0, 100),
+    ])
+    .protect(Pid::from_u32(13));
+    let kill_log = source.kill_log();
+    let renice_log = source.renice_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    monitor.set_search(String::from("worker"));
+    let _ = monitor.update(Message::SelectAllFiltered);
+    assert_eq!(monitor.selected_pids.len(), 3);
+
+    monitor.nice_value = 15;
+    assert_eq!(monitor.renice_selected(), (2, 3));
+    assert_eq!(
+        *renice_log.lock().unwrap(),
+        vec![(Pid::from_u32(11), 15), (Pid::from_u32(12), 15)]
+    );
+
+    let _ = monitor.update(Message::RequestKill);
+    assert_eq!(monitor.kill_target, KillTarget::Selection);
+    assert_eq!(monitor.kill_preview().len(), 3);
+    let (outcome, _) = monitor.signal_selected();
+    assert_eq!(outcome, KillOutcome::PermissionDenied);
+    let signaled: Vec<u32> = kill_log
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    assert_eq!(signaled, vec![11, 12, 13]);
+}
+
+// test 29: ring buffer keeps only the newest samples
+#[test]
+fn test_ring_buffer_bounded() {
+    let mut buffer = RingBuffer::new(3);
+    for value in 1..=5 {
+        buffer.push(value);
+    }
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    assert_eq!(buffer.last_n(2).copied().collect::<Vec<_>>(), vec![4, 5]);
+    buffer.set_capacity(1);
+    assert_eq!(buffer.latest(), Some(&5));
+    assert_eq!(buffer.len(), 1);
+}
+
+// test 30: per-process history accumulates across refreshes and drops exited PIDs
+#[test]
+fn test_process_history_across_refreshes() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("climber", 1, None, 10.0, 1000),
+        scripted_process("brief", 2, None, 5.0, 500),
+    ])
+    .then(vec![
+        scripted_process("climber", 1, None, 20.0, 2000),
+        scripted_process("brief", 2, None, 5.0, 500),
+    ])
+    .then(vec![scripted_process("climber", 1, None, 30.0, 3000)]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+    monitor.refresh_processes();
+
+    let history = monitor.process_history(Pid::from_u32(1)).unwrap();
+    let cpu: Vec<f32> = history.iter().map(|s| s.cpu_usage).collect();
+    assert_eq!(cpu, vec![10.0, 20.0, 30.0]);
+    assert_eq!(history.latest().unwrap().memory_usage, 3000);
+    assert!(monitor.process_history(Pid::from_u32(2)).is_none());
+
+    let _ = monitor.update(Message::HistoryRetentionChanged(60));
+    let history = monitor.process_history(Pid::from_u32(1)).unwrap();
+    assert_eq!(history.capacity(), 60);
+}
+
+// test 31: system samples are recorded on every refresh
+#[test]
+fn test_system_history_samples() {
+    let source = ScriptedSource::new(vec![])
+        .with_cpu_usages(vec![10.0, 30.0])
+        .with_total_memory(1000)
+        .with_used_memory(600)
+        .with_swap(500, 100);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+
+    let history = monitor.system_manager.history();
+    assert_eq!(history.len(), 2);
+    let sample = history.latest().unwrap();
+    assert_eq!(sample.cpu_usage, 20.0);
+    assert_eq!(sample.core_usages, vec![10.0, 30.0]);
+    assert_eq!(sample.used_memory, 600);
+    assert_eq!(sample.available_memory, 400);
+    assert_eq!((sample.used_swap, sample.total_swap), (100, 500));
+
+    assert_eq!(ChartWindow::OneMinute.samples(), 60);
+    assert_eq!(
+        ChartWindow::OneHour.samples(),
+        SystemManager::HISTORY_CAPACITY
+    );
+}
+
+// test 32: per-core usage and frequency history
+#[test]
+fn test_per_core_history() {
+    let source = ScriptedSource::new(vec![])
+        .with_cpu_frequencies(vec![2400, 3600])
+        .with_cpu_usages(vec![95.0, 5.0]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+    monitor.refresh_processes();
+
+    let manager = &monitor.system_manager;
+    assert_eq!(manager.cpu_cores, 2);
+    let latest = manager.history().latest().unwrap();
+    assert_eq!(latest.core_frequencies, vec![2400, 3600]);
+    assert_eq!(manager.core_history(0, 60), vec![95.0; 3]);
+    assert_eq!(manager.core_history(1, 2), vec![5.0; 2]);
+    // A core that does not exist reads as idle instead of panicking
+    assert_eq!(manager.core_history(7, 1), vec![0.0]);
+}
+
+// test 33: disks are listed by mount point with usage and I/O history
+#[test]
+fn test_disk_manager() {
+    let disk = |mount: &str, total: u64, available: u64, read: u64| DiskInfo {
+        name: format!("/dev/{}", mount.trim_start_matches('/')),
+        mount_point: PathBuf::from(mount),
+        file_system: String::from("ext4"),
+        total_space: total,
+        available_space: available,
+        is_removable: false,
+        read_bytes: read,
+        written_bytes: read * 2,
+    };
+    let source = ScriptedSource::new(vec![]).with_disks(vec![
+        disk("/var", 1000, 50, 10),
+        disk("/", 1000, 750, 20),
+        disk("/empty", 0, 0, 0),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+
+    let disks = monitor.disk_manager.disks();
+    let mounts: Vec<_> = disks.iter().map(|d| d.mount_point.clone()).collect();
+    assert_eq!(
+        mounts,
+        vec![
+            PathBuf::from("/"),
+            PathBuf::from("/empty"),
+            PathBuf::from("/var")
+        ]
+    );
+    assert_eq!(disks[0].used_space(), 250);
+    assert_eq!(disks[0].usage_percent(), 25.0);
+    assert_eq!(disks[1].usage_percent(), 0.0);
+    assert_eq!(disks[2].usage_percent(), 95.0);
+
+    let history = monitor.disk_manager.history(Path::new("/var")).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        *history.latest().unwrap(),
+        DiskIoSample {
+            read_bytes: 10,
+            written_bytes: 20
+        }
+    );
+
+    // An unmounted filesystem drops out of the list and its history is forgotten
+    let mut manager = DiskManager::new(10);
+    manager.update(vec![disk("/mnt/usb", 100, 100, 0)]);
+    manager.update(vec![]);
+    assert!(manager.disks().is_empty());
+    assert!(manager.history(Path::new("/mnt/usb")).is_none());
+}
+
+// test 34: network interfaces are listed by name with traffic history
+#[test]
+fn test_network_manager() {
+    let interface = |name: &str, received: u64, transmitted: u64| NetworkInfo {
+        name: name.to_string(),
+        mac_address: String::from("00:11:22:33:44:55"),
+        ip_addresses: vec![String::from("10.0.0.2/24")],
+        received,
+        transmitted,
+        total_received: received * 10,
+        total_transmitted: transmitted * 10,
+        total_packets_received: 5,
+        total_packets_transmitted: 3,
+        total_errors_received: 0,
+        total_errors_transmitted: 1,
+    };
+    let source = ScriptedSource::new(vec![]).with_networks(vec![
+        interface("wlan0", 100, 50),
+        interface("eth0", 2048, 1024),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+
+    let names: Vec<_> = monitor
+        .network_manager
+        .interfaces()
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["eth0", "wlan0"]);
+
+    let history = monitor.network_manager.history("eth0").unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        *history.latest().unwrap(),
+        NetworkSample {
+            received: 2048,
+            transmitted: 1024
+        }
+    );
+
+    // A removed interface loses its history
+    let mut manager = NetworkManager::new(10);
+    manager.update(vec![interface("tun0", 1, 1)]);
+    manager.update(vec![interface("eth0", 1, 1)]);
+    assert!(manager.history("tun0").is_none());
+    assert_eq!(manager.history("eth0").unwrap().len(), 1);
+}
+
+// test 35: sensor readings, colour levels and the no-sensor case
+#[test]
+fn test_sensor_levels_and_history() {
+    let sensor = |label: &str, temperature: Option<f32>, critical: Option<f32>| SensorInfo {
+        label: label.to_string(),
+        temperature,
+        max: None,
+        critical,
+    };
+    assert_eq!(
+        sensor("a", Some(50.0), Some(90.0)).level(),
+        SensorLevel::Normal
+    );
+    assert_eq!(
+        sensor("a", Some(85.0), Some(90.0)).level(),
+        SensorLevel::Warning
+    );
+    assert_eq!(
+        sensor("a", Some(95.0), Some(90.0)).level(),
+        SensorLevel::Critical
+    );
+    // Without a reported critical value the default threshold applies
+    assert_eq!(sensor("a", Some(95.0), None).level(), SensorLevel::Warning);
+    assert_eq!(sensor("a", None, Some(90.0)).level(), SensorLevel::Unknown);
+
+    let source = ScriptedSource::new(vec![]).with_sensors(vec![
+        sensor("Core 1", Some(60.0), Some(100.0)),
+        sensor("Core 0", Some(55.0), Some(100.0)),
+        sensor("acpitz", None, None),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+
+    let labels: Vec<_> = monitor
+        .sensor_manager
+        .sensors()
+        .iter()
+        .map(|s| s.label.as_str())
+        .collect();
+    assert_eq!(labels, vec!["Core 0", "Core 1", "acpitz"]);
+    let history: Vec<f32> = monitor
+        .sensor_manager
+        .history("Core 1")
+        .unwrap()
+        .iter()
+        .copied()
+        .collect();
+    assert_eq!(history, vec![60.0, 60.0]);
+    // A sensor without a reading has nothing to chart
+    assert!(monitor.sensor_manager.history("acpitz").is_none());
+
+    // VMs usually expose no sensors at all
+    let monitor = TarnerMonitor::with_source(ScriptedSource::new(vec![]));
+    assert!(monitor.sensor_manager.sensors().is_empty());
+}
+
+// test 36: load average, uptime and process totals with trends
+#[test]
+fn test_system_health_and_trends() {
+    let source = ScriptedSource::new(vec![scripted_process("init", 1, None, 0.0, 1)])
+        .with_load_average(1.5, 0.75, 0.25)
+        .with_boot_time(1_700_000_000, 3600)
+        .with_thread_count(12)
+        .then(vec![
+            scripted_process("init", 1, None, 0.0, 1),
+            scripted_process("sshd", 2, Some(1), 0.0, 1),
+        ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    let manager = &monitor.system_manager;
+    assert_eq!(manager.boot_time, 1_700_000_000);
+    // A single sample has nothing to compare against
+    assert_eq!(manager.trend(|s| s.process_count as f64), Trend::Steady);
+
+    monitor.refresh_processes();
+    let manager = &monitor.system_manager;
+    let latest = manager.history().latest().unwrap();
+    assert_eq!(latest.load_average.one, 1.5);
+    assert_eq!(latest.load_average.fifteen, 0.25);
+    assert_eq!(latest.uptime, 3600);
+    assert_eq!(latest.process_count, 2);
+    assert_eq!(latest.thread_count, 12);
+    assert_eq!(manager.trend(|s| s.process_count as f64), Trend::Up);
+    assert_eq!(manager.trend(|s| s.load_average.one), Trend::Steady);
+
+    assert_eq!(Trend::between(2.0, 1.0), Trend::Down);
+    assert_eq!(Trend::between(1.0, 1.001), Trend::Steady);
+}
+
+// test 37: extended process fields in the CSV export
+#[test]
+fn test_csv_includes_extended_fields() {
+    let mut python = scripted_process("python3", 42, Some(1), 40.0, 2048);
+    python.command_line = String::from("python3 manage.py runserver");
+    python.exe = Some(PathBuf::from("/usr/bin/python3"));
+    python.cwd = Some(PathBuf::from("/srv/app"));
+    python.user = Some(String::from("www"));
+    python.uid = Some(33);
+    python.gid = Some(33);
+    python.start_time = 1_700_000_000;
+    python.thread_count = 4;
+
+    let mut bare = scripted_process("kworker", 7, None, 0.0, 0);
+    bare.uid = Some(0);
+    assert_eq!(python.user_label(), "www");
+    assert_eq!(bare.user_label(), "0");
+
+    let mut output = Vec::new();
+    write_csv(&mut output, &[python, bare], 4, 4096).unwrap();
+    let csv = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(
+        lines[0].ends_with("Threads,Start Time (unix),Executable,Working Directory,Command Line")
+    );
+    assert!(
+        lines[1].ends_with(
+            "www,33,33,4,1700000000,/usr/bin/python3,/srv/app,python3 manage.py runserver"
+        )
+    );
+    assert!(lines[2].ends_with(",0,N/A,1,0,,,"));
+}
+
+// test 38: choosing, reordering and resizing process table columns
+#[test]
+fn test_configurable_columns() {
+    let mut process = scripted_process("postgres", 300, Some(1), 50.0, 512 * 1024 * 1024);
+    process.virtual_memory = 2048 * 1024 * 1024;
+    process.run_time = 90_061;
+    assert_eq!(ProcessColumn::Cpu.value(&process, 4, 1024), "12.50");
+    assert_eq!(ProcessColumn::ParentPid.value(&process, 4, 1024), "1");
+    assert_eq!(
+        ProcessColumn::VirtualMemory.value(&process, 4, 1024),
+        "2048.0 MB"
+    );
+    assert_eq!(
+        ProcessColumn::Runtime.value(&process, 4, 1024),
+        "1d 01:01:01"
+    );
+
+    let mut monitor = TarnerMonitor::with_source(ScriptedSource::new(vec![]));
+    let shown = |monitor: &TarnerMonitor| -> Vec<ProcessColumn> {
+        monitor.columns.iter().map(|c| c.column).collect()
+    };
+    assert_eq!(
+        shown(&monitor)[..3],
+        [
+            ProcessColumn::Pid,
+            ProcessColumn::Cpu,
+            ProcessColumn::MemoryPercent
+        ]
+    );
+
+    let _ = monitor.update(Message::ToggleColumn(ProcessColumn::User));
+    let _ = monitor.update(Message::ToggleColumn(ProcessColumn::Threads));
+    let _ = monitor.update(Message::ToggleColumn(ProcessColumn::CommandLine));
+    let _ = monitor.update(Message::ToggleColumn(ProcessColumn::VirtualMemory));
+    assert_eq!(
+        shown(&monitor),
+        vec![
+            ProcessColumn::Pid,
+            ProcessColumn::Cpu,
+            ProcessColumn::MemoryPercent,
+            ProcessColumn::VirtualMemory
+        ]
+    );
+
+    // Moves stop at either end of the table
+    let _ = monitor.update(Message::MoveColumn(ProcessColumn::VirtualMemory, -2));
+    let _ = monitor.update(Message::MoveColumn(ProcessColumn::Pid, 10));
+    let _ = monitor.update(Message::MoveColumn(ProcessColumn::Cpu, -5));
+    assert_eq!(
+        shown(&monitor),
+        vec![
+            ProcessColumn::Cpu,
+            ProcessColumn::VirtualMemory,
+            ProcessColumn::MemoryPercent,
+            ProcessColumn::Pid
+        ]
+    );
+
+    // Widths are clamped to the allowed range
+    let _ = monitor.update(Message::ResizeColumn(ProcessColumn::Pid, -1000.0));
+    let _ = monitor.update(Message::ResizeColumn(ProcessColumn::Cpu, 20.0));
+    assert_eq!(monitor.columns[3].width, columns::MIN_WIDTH);
+    assert_eq!(
+        monitor.columns[0].width,
+        ProcessColumn::Cpu.default_width() + 20.0
+    );
+}
+
+// test 39: header clicks, secondary sort keys and the PID tiebreaker
+#[test]
+fn test_multi_key_sort() {
+    let processes = vec![
+        scripted_process("worker", 30, None, 10.0, 100),
+        scripted_process("worker", 10, None, 10.0, 300),
+        scripted_process("api", 20, None, 10.0, 200),
+        scripted_process("db", 40, None, 90.0, 100),
+    ];
+    let mut monitor = TarnerMonitor::with_source(ScriptedSource::new(processes));
+    let pids = |monitor: &TarnerMonitor| -> Vec<u32> {
+        monitor.processes.iter().map(|p| p.pid.as_u32()).collect()
+    };
+    let cpu = SortField::Column(ProcessColumn::Cpu);
+    let memory = SortField::Column(ProcessColumn::Memory);
+
+    // Equal names fall back to PID order
+    assert_eq!(pids(&monitor), vec![20, 40, 10, 30]);
+
+    // First click sorts ascending, the second flips it; ties stay in PID order
+    let _ = monitor.update(Message::SortClicked(cpu));
+    assert_eq!(pids(&monitor), vec![10, 20, 30, 40]);
+    let _ = monitor.update(Message::SortClicked(cpu));
+    assert_eq!(monitor.current_sort, vec![SortKey::descending(cpu)]);
+    assert_eq!(pids(&monitor), vec![40, 10, 20, 30]);
+
+    // Shift-click adds memory as a secondary key, a second shift-click flips it
+    let _ = monitor.update(Message::EventOccurred(iced::Event::Keyboard(
+        iced::keyboard::Event::ModifiersChanged(Modifiers::SHIFT),
+    )));
+    let _ = monitor.update(Message::SortClicked(memory));
+    assert_eq!(pids(&monitor), vec![40, 30, 20, 10]);
+    let _ = monitor.update(Message::SortClicked(memory));
+    assert_eq!(
+        monitor.current_sort,
+        vec![SortKey::descending(cpu), SortKey::descending(memory)]
+    );
+    assert_eq!(pids(&monitor), vec![40, 10, 20, 30]);
+
+    // Ordering is the same after a refresh tick
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    assert_eq!(pids(&monitor), vec![40, 10, 20, 30]);
+
+    // A plain click replaces all keys
+    let _ = monitor.update(Message::EventOccurred(iced::Event::Keyboard(
+        iced::keyboard::Event::ModifiersChanged(Modifiers::empty()),
+    )));
+    let _ = monitor.update(Message::SortClicked(SortField::Name));
+    assert_eq!(
+        monitor.current_sort,
+        vec![SortKey::ascending(SortField::Name)]
+    );
+}
+
+// test 40: filter queries with fields, units and boolean operators
+#[test]
+fn test_filter_query_language() {
+    let mut sshd = scripted_process("sshd", 100, Some(1), 8.0, 20 * 1024 * 1024);
+    sshd.user = Some(String::from("root"));
+    let mut java = scripted_process("java", 200, Some(1), 120.0, 2048 * 1024 * 1024);
+    java.user = Some(String::from("alice"));
+    java.command_line = String::from("java -jar app.jar --port=8080");
+    let mut ssh_agent = scripted_process("ssh-agent", 300, Some(200), 0.0, 1024 * 1024);
+    ssh_agent.user = Some(String::from("alice"));
+    ssh_agent.status = ProcessStatus::Zombie;
+
+    let source = ScriptedSource::new(vec![sshd, java, ssh_agent]).with_cpu_cores(4);
+    let mut monitor = TarnerMonitor::with_source(source);
+    let mut filter = |query: &str| -> Vec<u32> {
+        monitor.set_search(query.to_string());
+        assert!(monitor.query().is_ok(), "'{}' should parse", query);
+        let mut pids: Vec<u32> = monitor
+            .get_filtered()
+            .iter()
+            .map(|p| p.pid.as_u32())
+            .collect();
+        pids.sort();
+        pids
+    };
+
+    assert_eq!(filter(""), vec![100, 200, 300]);
+    assert_eq!(filter("SSH"), vec![100, 300]);
+    // CPU is compared as the share of all cores: 120% on 4 cores is 30%
+    assert_eq!(filter("cpu>20"), vec![200]);
+    assert_eq!(filter("cpu>=2 cpu<=2"), vec![100]);
+    assert_eq!(filter("mem>500MB"), vec![200]);
+    assert_eq!(filter("mem<1.5mb"), vec![300]);
+    assert_eq!(filter("user:alice mem>1GB"), vec![200]);
+    assert_eq!(filter("user:root OR status:zombie"), vec![100, 300]);
+    assert_eq!(filter("name~^ssh"), vec![100, 300]);
+    assert_eq!(filter("name~^ssh AND NOT user:root"), vec![300]);
+    assert_eq!(filter("pid:200"), vec![200]);
+    assert_eq!(filter("ppid:1"), vec![100, 200]);
+    assert_eq!(filter("ppid!=1"), vec![300]);
+    assert_eq!(filter("cmd~--port=\\d+"), vec![200]);
+    assert_eq!(filter("(user:root OR cpu>20) NOT name:java"), vec![100]);
+    assert_eq!(filter("cmd:\"app.jar --port\""), vec![200]);
+}
+
+// test 41: malformed queries report an error and fall back to a name search
+#[test]
+fn test_filter_query_errors() {
+    let error = |query: &str| Query::parse(query).unwrap_err();
+
+    assert_eq!(error("cpu>").message, "missing value after 'cpu>'");
+    assert_eq!(error("cpu>").position, 4);
+    assert!(error("size>10").message.starts_with("unknown field 'size'"));
+    assert!(error("mem>10XB").message.contains("memory size"));
+    assert!(error("pid:abc").message.contains("whole number"));
+    assert!(
+        error("name~[a")
+            .message
+            .starts_with("invalid regular expression")
+    );
+    assert!(error("user>root").message.contains("cannot be compared"));
+    assert_eq!(error("(cpu>1").message, "unclosed '('");
+    assert_eq!(error("cpu>1)").message, "unexpected ')'");
+    assert_eq!(error("cpu>1 OR").message, "expected a filter term");
+    assert_eq!(error("name:\"ssh").message, "unclosed quote");
+    assert_eq!(
+        error("mem>10XB").to_string(),
+        "expected a memory size such as 500MB, found '10XB' (at column 5)"
+    );
+
+    let source = ScriptedSource::new(vec![
+        scripted_process("cpu>", 1, None, 0.0, 0),
+        scripted_process("bash", 2, None, 0.0, 0),
+    ]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.set_search(String::from("cpu>"));
+    assert!(monitor.query().is_err());
+    let names: Vec<_> = monitor
+        .get_filtered()
+        .iter()
+        .map(|p| p.name.to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["cpu>"]);
+}
+
+// test 42: regex, case-sensitive and full command line search modes
+#[test]
+fn test_search_modes() {
+    let mut python = scripted_process("python3", 10, None, 0.0, 0);
+    python.command_line = String::from("python3 manage.py runserver");
+    python.exe = Some(PathBuf::from("/usr/bin/python3"));
+    let mut server = scripted_process("Server", 20, None, 0.0, 0);
+    server.exe = Some(PathBuf::from("/opt/app/bin/Server"));
+    let mut bash = scripted_process("bash", 30, None, 0.0, 0);
+    bash.command_line = String::from("bash -c 'echo [ok]'");
+
+    let source = ScriptedSource::new(vec![python, server, bash]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    let filter = |monitor: &mut TarnerMonitor, search: &str| -> Vec<u32> {
+        let _ = monitor.update(Message::SearchChanged(search.to_string()));
+        let mut pids: Vec<u32> = monitor
+            .get_filtered()
+            .iter()
+            .map(|p| p.pid.as_u32())
+            .collect();
+        pids.sort();
+        pids
+    };
+
+    // Defaults: words match names, ignoring case
+    assert_eq!(monitor.search_options(), SearchOptions::default());
+    assert_eq!(filter(&mut monitor, "server"), vec![20]);
+    assert_eq!(filter(&mut monitor, "manage"), Vec::<u32>::new());
+    assert_eq!(filter(&mut monitor, "name:SERVER"), vec![20]);
+    assert_eq!(filter(&mut monitor, "name~^serv"), vec![20]);
+
+    let _ = monitor.update(Message::ToggleSearchFullCommand);
+    assert_eq!(filter(&mut monitor, "server"), vec![10, 20]);
+    assert_eq!(filter(&mut monitor, "/opt/app"), vec![20]);
+
+    let _ = monitor.update(Message::ToggleSearchCase);
+    assert_eq!(filter(&mut monitor, "server"), vec![10]);
+    assert_eq!(filter(&mut monitor, "Server"), vec![20]);
+    // Text fields and field regexes follow the same setting
+    assert_eq!(filter(&mut monitor, "name:server"), Vec::<u32>::new());
+    assert_eq!(filter(&mut monitor, "name=Server"), vec![20]);
+    assert_eq!(filter(&mut monitor, "name!=Server"), vec![10, 30]);
+    assert_eq!(filter(&mut monitor, "name~^serv"), Vec::<u32>::new());
+    assert_eq!(filter(&mut monitor, "name~^Serv"), vec![20]);
+
+    // Regex mode takes the whole input as one pattern, spaces and parentheses included
+    let _ = monitor.update(Message::ToggleSearchRegex);
+    assert_eq!(filter(&mut monitor, "^(bash|Server)$"), vec![20, 30]);
+    assert_eq!(filter(&mut monitor, "manage\\.py run"), vec![10]);
+    assert_eq!(filter(&mut monitor, "cpu>5"), Vec::<u32>::new());
+
+    // An invalid pattern is reported and searched for literally
+    assert_eq!(filter(&mut monitor, "echo [ok"), vec![30]);
+    let error = monitor.query().unwrap_err();
+    assert!(error.message.starts_with("invalid regular expression"));
+
+    let _ = monitor.update(Message::ToggleSearchCase);
+    assert_eq!(filter(&mut monitor, "^SERVER$"), vec![20]);
+    assert_eq!(
+        monitor.search_options(),
+        SearchOptions {
+            regex: true,
+            case_sensitive: false,
+            full_command: true,
+        }
+    );
+    assert!(monitor.search_options().describe().starts_with("regex"));
+}
+
+// test 43: filter presets save and restore search, sort and columns
+#[test]
+fn test_filter_presets() {
+    let mut zombie = scripted_process("defunct", 30, Some(1), 0.0, 0);
+    zombie.status = ProcessStatus::Zombie;
+    let source = ScriptedSource::new(vec![
+        scripted_process("nginx", 10, Some(1), 50.0, 100),
+        scripted_process("postgres", 20, Some(1), 5.0, 900),
+        zombie,
+    ])
+    .with_cpu_cores(2);
+    let mut monitor = TarnerMonitor::with_source(source);
+    let names = |monitor: &TarnerMonitor| -> Vec<String> {
+        monitor
+            .get_filtered()
+            .iter()
+            .map(|p| p.name.to_string_lossy().to_string())
+            .collect()
+    };
+
+    // Built-in presets are offered on a fresh config
+    let built_in: Vec<&str> = monitor.presets.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(built_in, vec!["Heavy hitters", "Zombies"]);
+    let _ = monitor.update(Message::PresetSelected(String::from("Zombies")));
+    assert_eq!(names(&monitor), vec!["defunct"]);
+    assert_eq!(monitor.active_preset.as_deref(), Some("Zombies"));
+
+    // Saving without a name is refused
+    let _ = monitor.update(Message::PresetNameChanged(String::from("  ")));
+    let _ = monitor.update(Message::SavePreset);
+    assert_eq!(monitor.presets.len(), 2);
+    assert!(matches!(monitor.toast, Some((_, ToastType::Error))));
+
+    let _ = monitor.update(Message::SearchChanged(String::from("nginx OR postgres")));
+    let _ = monitor.update(Message::SortClicked(SortField::Column(
+        ProcessColumn::Memory,
+    )));
+    let _ = monitor.update(Message::SortClicked(SortField::Column(
+        ProcessColumn::Memory,
+    )));
+    let _ = monitor.update(Message::ToggleColumn(ProcessColumn::DiskRead));
+    let _ = monitor.update(Message::PresetNameChanged(String::from("our services")));
+    let _ = monitor.update(Message::SavePreset);
+    assert_eq!(monitor.presets.len(), 3);
+    let saved = monitor.presets.last().unwrap().clone();
+    assert_eq!(saved.name, "our services");
+    assert_eq!(saved.search, "nginx OR postgres");
+    assert_eq!(
+        saved.sort,
+        vec![SortKey::descending(SortField::Column(
+            ProcessColumn::Memory
+        ))]
+    );
+    assert_eq!(saved.columns, monitor.columns);
+
+    // Switching away and back restores everything the preset captured
+    let _ = monitor.update(Message::PresetSelected(String::from("Heavy hitters")));
+    assert_eq!(names(&monitor), vec!["nginx"]);
+    assert_ne!(monitor.columns, saved.columns);
+    let _ = monitor.update(Message::PresetSelected(String::from("our services")));
+    assert_eq!(names(&monitor), vec!["postgres", "nginx"]);
+    assert_eq!(monitor.columns, saved.columns);
+    assert_eq!(monitor.preset_name, "our services");
+
+    // Saving under an existing name overwrites it, ignoring case
+    let _ = monitor.update(Message::SearchChanged(String::from("nginx")));
+    let _ = monitor.update(Message::PresetNameChanged(String::from("Our Services")));
+    let _ = monitor.update(Message::SavePreset);
+    assert_eq!(monitor.presets.len(), 3);
+    assert_eq!(monitor.presets[2].search, "nginx");
+
+    // Selecting and deleting match names the same way
+    let _ = monitor.update(Message::PresetSelected(String::from("OUR SERVICES")));
+    assert_eq!(monitor.active_preset.as_deref(), Some("Our Services"));
+    assert!(!presets::remove(&mut monitor.presets.clone(), "Heavy"));
+    assert!(presets::remove(
+        &mut monitor.presets.clone(),
+        "heavy HITTERS"
+    ));
+
+    // A delete that finds nothing leaves the active preset alone
+    let all = std::mem::take(&mut monitor.presets);
+    let _ = monitor.update(Message::DeletePreset);
+    assert_eq!(monitor.active_preset.as_deref(), Some("Our Services"));
+    monitor.presets = all;
+
+    let _ = monitor.update(Message::DeletePreset);
+    assert_eq!(monitor.presets.len(), 2);
+    assert_eq!(monitor.active_preset, None);
+    let _ = monitor.update(Message::PresetSelected(String::from("Our Services")));
+    assert_eq!(monitor.search_str(), "nginx");
+}
+
+// test 44: alert rules wait out their duration, resolve with hysteresis and respect cooldown
+#[test]
+fn test_alert_rule_engine() {
+    let rule = AlertRule {
+        name: String::from("Hot java"),
+        condition: AlertCondition::ProcessCpuAbove {
+            filter: String::from("name:java"),
+            percent: 90.0,
+        },
+        enabled: true,
+        duration_secs: 30,
+        hysteresis: 10.0,
+        cooldown_secs: 120,
+        actions: Vec::new(),
+        dry_run: false,
+    };
+    let mut engine = AlertEngine::new(vec![rule.clone()]);
+    let context = QueryContext {
+        cpu_cores: 1,
+        total_memory: 1024,
+    };
+    let start = Instant::now();
+    let mut check = |java_cpu: f32, secs: u64| -> Vec<AlertKind> {
+        let processes = vec![
+            scripted_process("java", 10, None, java_cpu, 0),
+            scripted_process("python", 11, None, 99.0, 0),
+        ];
+        let inputs = AlertInputs {
+            processes: &processes,
+            sample: None,
+            disks: &[],
+            context,
+        };
+        engine
+            .evaluate(&inputs, start + Duration::from_secs(secs))
+            .iter()
+            .map(|e| e.kind)
+            .collect()
+    };
+
+    // Breached, but not for long enough yet; a dip restarts the clock
+    assert!(check(95.0, 0).is_empty());
+    assert!(check(95.0, 20).is_empty());
+    assert!(check(50.0, 25).is_empty());
+    assert!(check(95.0, 30).is_empty());
+    assert_eq!(check(95.0, 60), vec![AlertKind::Fired]);
+    assert!(check(99.0, 61).is_empty());
+    // Inside the hysteresis band the alert stays active
+    assert!(check(85.0, 62).is_empty());
+    assert_eq!(check(79.0, 63), vec![AlertKind::Resolved]);
+    // Breached again for long enough, but still cooling down from the first firing
+    assert!(check(95.0, 70).is_empty());
+    assert!(check(95.0, 150).is_empty());
+    assert_eq!(check(95.0, 180), vec![AlertKind::Fired]);
+
+    let history: Vec<_> = engine.history().iter().map(|e| e.kind).collect();
+    assert_eq!(
+        history,
+        vec![AlertKind::Fired, AlertKind::Resolved, AlertKind::Fired]
+    );
+    let fired = engine.history().latest().unwrap();
+    assert_eq!(fired.rule, "Hot java");
+    assert!(fired.message.contains("java (PID 10) at 95.0%"));
+    assert_eq!(engine.status(0), RuleStatus::Firing);
+
+    // Rules survive a trip through the config file
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Config {
+        rules: Vec<AlertRule>,
+    }
+    let mut rules = alerts::default_rules();
+    rules.push(rule);
+    let saved = toml::to_string(&Config {
+        rules: rules.clone(),
+    })
+    .unwrap();
+    let loaded: Config = toml::from_str(&saved).unwrap();
+    assert_eq!(loaded.rules, rules);
+    let minimal: Config = toml::from_str(
+        "[[rules]]\nname = \"Low /\"\ncondition = { kind = \"DiskFreeBelow\", mount_point = \"/\", gigabytes = 2.5 }\n",
+    )
+    .unwrap();
+    assert!(minimal.rules[0].enabled);
+    assert_eq!(minimal.rules[0].cooldown_secs, 0);
+}
+
+// test 45: alerts evaluated on refresh show toasts, history and rule status
+#[test]
+fn test_alerts_on_refresh() {
+    let busy = |cpu: f32| vec![scripted_process("miner", 42, None, cpu, 0)];
+    let source = ScriptedSource::new(busy(10.0))
+        .with_cpu_cores(1)
+        .then(busy(97.0))
+        .then(busy(97.0))
+        .then(busy(97.0))
+        .then(busy(85.0))
+        .then(busy(40.0));
+    let mut monitor = TarnerMonitor::with_source(source);
+    let runaway = monitor
+        .alert_engine
+        .rules()
+        .iter()
+        .position(|r| r.name == "Runaway process")
+        .unwrap();
+    assert!(Tab::ALL.contains(&Tab::Alerts));
+
+    let start = Instant::now();
+    let _ = monitor.update(Message::RefreshTick(start));
+    assert_eq!(monitor.alert_engine.status(runaway), RuleStatus::Pending);
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(15)));
+    assert!(monitor.toast.is_none());
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(31)));
+    assert_eq!(monitor.alert_engine.status(runaway), RuleStatus::Firing);
+    match &monitor.toast {
+        Some((message, ToastType::Error)) => assert!(message.contains("miner (PID 42)")),
+        other => panic!(
+            "expected an alert toast, got {:?}",
+            other.as_ref().map(|t| &t.0)
+        ),
+    }
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(32)));
+    assert_eq!(monitor.alert_engine.status(runaway), RuleStatus::Firing);
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(33)));
+    assert_eq!(monitor.alert_engine.status(runaway), RuleStatus::Ok);
+    assert!(matches!(monitor.toast, Some((_, ToastType::Success))));
+    assert_eq!(monitor.alert_engine.history().len(), 2);
+
+    // Disabled rules are skipped; clearing empties the history
+    let _ = monitor.update(Message::ToggleAlertRule(runaway));
+    assert_eq!(monitor.alert_engine.status(runaway), RuleStatus::Disabled);
+    assert!(!monitor.alert_engine.rules()[runaway].enabled);
+    let _ = monitor.update(Message::ClearAlertHistory);
+    assert!(monitor.alert_engine.history().is_empty());
+}
+
+// test 46: firing rules run their actions, or only log them in dry-run mode
+#[test]
+fn test_alert_actions() {
+    let snapshot_dir =
+        std::env::temp_dir().join(format!("tarner-snapshots-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&snapshot_dir);
+    let rule = |dry_run: bool| AlertRule {
+        name: String::from("Runaway worker"),
+        condition: AlertCondition::ProcessCpuAbove {
+            filter: String::from("name:worker"),
+            percent: 50.0,
+        },
+        enabled: true,
+        duration_secs: 0,
+        hysteresis: 0.0,
+        cooldown_secs: 0,
+        actions: vec![
+            AlertAction::Renice { nice: 10 },
+            AlertAction::Signal {
+                signal: KillSignal::Term,
+            },
+            AlertAction::Snapshot {
+                directory: Some(snapshot_dir.clone()),
+            },
+        ],
+        dry_run,
+    };
+    let processes = || {
+        vec![
+            scripted_process("worker", 7, None, 90.0, 0),
+            scripted_process("shell", 8, None, 1.0, 0),
+        ]
+    };
+    let action_messages = |monitor: &TarnerMonitor| -> Vec<String> {
+        monitor
+            .alert_engine
+            .history()
+            .iter()
+            .filter(|e| e.kind == AlertKind::Action)
+            .map(|e| e.message.clone())
+            .collect()
+    };
+
+    // Dry run: nothing is touched, but every action is recorded
+    let source = ScriptedSource::new(processes()).with_cpu_cores(1);
+    let (kills, renices) = (source.kill_log(), source.renice_log());
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.alert_engine = AlertEngine::new(vec![rule(true)]);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    assert!(kills.lock().unwrap().is_empty());
+    assert!(renices.lock().unwrap().is_empty());
+    assert!(!snapshot_dir.exists());
+    assert_eq!(
+        action_messages(&monitor),
+        vec![
+            "Runaway worker: [dry run] would renice to 10 for worker (PID 7)",
+            "Runaway worker: [dry run] would send SIGTERM for worker (PID 7)",
+            "Runaway worker: [dry run] would export a process snapshot for worker (PID 7)",
+        ]
+    );
+
+    // For real: the breaching process is reniced and signalled, and a snapshot written
+    let source = ScriptedSource::new(processes()).with_cpu_cores(1);
+    let (kills, renices) = (source.kill_log(), source.renice_log());
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.alert_engine = AlertEngine::new(vec![rule(false)]);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    assert_eq!(*renices.lock().unwrap(), vec![(Pid::from_u32(7), 10)]);
+    assert_eq!(
+        *kills.lock().unwrap(),
+        vec![(Pid::from_u32(7), KillSignal::Term)]
+    );
+    let messages = action_messages(&monitor);
+    assert_eq!(messages[0], "Runaway worker: set nice 10 on worker (PID 7)");
+    assert_eq!(
+        messages[1],
+        "Runaway worker: sent SIGTERM to worker (PID 7)"
+    );
+    assert!(messages[2].starts_with("Runaway worker: saved snapshot to "));
+    let snapshot = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let contents = std::fs::read_to_string(&snapshot).unwrap();
+    assert!(contents.lines().any(|line| line.contains("worker")));
+    std::fs::remove_dir_all(&snapshot_dir).unwrap();
+
+    // Signalling needs a process; system-wide rules record the failure instead
+    let mut system_rule = rule(false);
+    system_rule.condition = AlertCondition::SystemMemoryAbove { percent: 50.0 };
+    system_rule.actions = vec![AlertAction::Signal {
+        signal: KillSignal::Kill,
+    }];
+    let source = ScriptedSource::new(processes())
+        .with_total_memory(100)
+        .with_used_memory(90);
+    let kills = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.alert_engine = AlertEngine::new(vec![system_rule]);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    assert!(kills.lock().unwrap().is_empty());
+    assert_eq!(
+        action_messages(&monitor),
+        vec!["Runaway worker: failed to send SIGKILL: the rule does not measure a process"]
+    );
+}
+
+// test 47: command actions get the alert details in their environment
+#[cfg(unix)]
+#[test]
+fn test_alert_command_action() {
+    let output = std::env::temp_dir().join(format!("tarner-alert-{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&output);
+    let mut rule = alerts::default_rules().remove(0);
+    rule.duration_secs = 0;
+    rule.actions = vec![AlertAction::Command {
+        command: format!(
+            "echo \"$TARNER_RULE|$TARNER_PID|$TARNER_PROCESS_NAME|$TARNER_VALUE\" > {}",
+            output.display()
+        ),
+    }];
+    let source =
+        ScriptedSource::new(vec![scripted_process("spinner", 99, None, 95.0, 0)]).with_cpu_cores(1);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.alert_engine = AlertEngine::new(vec![rule]);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+
+    // The command runs in the background; give it a moment to write
+    let mut contents = String::new();
+    for _ in 0..50 {
+        contents = std::fs::read_to_string(&output).unwrap_or_default();
+        if contents.ends_with('\n') {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(contents.trim(), "Runaway process|99|spinner|95.00");
+    let _ = std::fs::remove_file(&output);
+}
+
+// test 48: diffing refreshes records started and exited processes, including PID reuse
+#[test]
+fn test_process_lifecycle_events() {
+    let mut worker = scripted_process("worker", 50, Some(1), 40.0, 2 * 1024 * 1024);
+    worker.start_time = 1000;
+    worker.run_time = 65;
+    let mut restarted = scripted_process("worker", 50, Some(1), 0.0, 0);
+    restarted.start_time = 2000;
+    let init = scripted_process("init", 1, None, 0.0, 0);
+    let cron = scripted_process("cron", 60, Some(1), 0.0, 0);
+
+    let source = ScriptedSource::new(vec![init.clone()])
+        .with_cpu_cores(1)
+        .then(vec![init.clone(), worker.clone()])
+        .then(vec![init.clone(), worker.clone(), cron.clone()])
+        .then(vec![init.clone(), cron.clone()])
+        .then(vec![init.clone(), cron.clone(), restarted])
+        .then(vec![init.clone(), cron.clone(), worker]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    // Processes already running at startup are not reported
+    assert!(monitor.event_feed.events().is_empty());
+    for _ in 0..5 {
+        monitor.refresh_processes();
+    }
+
+    let summary = |monitor: &TarnerMonitor| -> Vec<(LifecycleKind, u32)> {
+        monitor
+            .filtered_events()
+            .iter()
+            .map(|e| (e.kind, e.process.pid.as_u32()))
+            .collect()
+    };
+    // Newest first; a reused PID is an exit followed by a start
+    assert_eq!(
+        summary(&monitor),
+        vec![
+            (LifecycleKind::Started, 50),
+            (LifecycleKind::Exited, 50),
+            (LifecycleKind::Started, 50),
+            (LifecycleKind::Exited, 50),
+            (LifecycleKind::Started, 60),
+            (LifecycleKind::Started, 50),
+        ]
+    );
+
+    // Exits keep the last-seen details of the process
+    let exit = monitor
+        .event_feed
+        .events()
+        .iter()
+        .find(|e| e.kind == LifecycleKind::Exited)
+        .unwrap();
+    assert_eq!(exit.process.name, OsString::from("worker"));
+    assert_eq!(exit.process.parent_pid, Some(Pid::from_u32(1)));
+    assert_eq!(exit.process.run_time, 65);
+    assert_eq!(exit.process.cpu_usage, 40.0);
+    assert_eq!(exit.process.memory_usage, 2 * 1024 * 1024);
+
+    let _ = monitor.update(Message::EventKindSelected(Some(LifecycleKind::Exited)));
+    assert_eq!(summary(&monitor).len(), 2);
+    let _ = monitor.update(Message::EventKindSelected(None));
+    let _ = monitor.update(Message::EventFilterChanged(String::from("cron")));
+    assert_eq!(summary(&monitor), vec![(LifecycleKind::Started, 60)]);
+    let _ = monitor.update(Message::EventFilterChanged(String::from(
+        "name:worker cpu>10",
+    )));
+    assert_eq!(
+        summary(&monitor),
+        vec![
+            (LifecycleKind::Started, 50),
+            (LifecycleKind::Exited, 50),
+            (LifecycleKind::Started, 50),
+        ]
+    );
+    let _ = monitor.update(Message::ClearEvents);
+    assert!(monitor.event_feed.events().is_empty());
+}
+
+// test 49: the watchdog restarts a lost process with doubling backoff until the budget runs out
+#[test]
+fn test_watchdog_backoff_and_budget() {
+    let mut entry = WatchEntry::new(
+        WatchTarget::CommandLine(String::from("manage.py runserver")),
+        String::from("python manage.py runserver"),
+    );
+    entry.max_restarts = 3;
+    entry.backoff_secs = 2;
+    entry.max_backoff_secs = 5;
+    entry.stable_secs = 30;
+    assert_eq!(entry.backoff(0), Duration::from_secs(2));
+    assert_eq!(entry.backoff(1), Duration::from_secs(4));
+    assert_eq!(entry.backoff(2), Duration::from_secs(5));
+
+    let mut server = scripted_process("python", 10, None, 0.0, 0);
+    server.command_line = String::from("python manage.py runserver 8000");
+    let up = vec![server];
+    let down: Vec<ProcessInfo> = vec![];
+    let mut watchdog = Watchdog::new(vec![entry]);
+    let start = Instant::now();
+    let mut check = |processes: &[ProcessInfo], secs: u64| {
+        watchdog.check(processes, start + Duration::from_secs(secs))
+    };
+
+    // Nothing happens until the process has been seen running
+    assert!(check(&down, 0).is_empty());
+    assert!(check(&up, 1).is_empty());
+    assert_eq!(
+        check(&down, 2),
+        vec![WatchEvent::Lost {
+            index: 0,
+            retry_in: Duration::from_secs(2)
+        }]
+    );
+    assert!(check(&down, 3).is_empty());
+    assert_eq!(
+        check(&down, 4),
+        vec![WatchEvent::Restart {
+            index: 0,
+            attempt: 1
+        }]
+    );
+    // The restarted process died straight away: wait longer each time
+    assert_eq!(
+        check(&down, 5),
+        vec![WatchEvent::Lost {
+            index: 0,
+            retry_in: Duration::from_secs(4)
+        }]
+    );
+    assert_eq!(
+        check(&down, 9),
+        vec![WatchEvent::Restart {
+            index: 0,
+            attempt: 2
+        }]
+    );
+    // Back up, but not for long enough to refill the budget
+    assert!(check(&up, 10).is_empty());
+    assert_eq!(watchdog.status(0), WatchStatus::Running);
+    assert_eq!(watchdog.restarts(0), 2);
+    let mut check = |processes: &[ProcessInfo], secs: u64| {
+        watchdog.check(processes, start + Duration::from_secs(secs))
+    };
+    assert!(matches!(check(&down, 11)[..], [WatchEvent::Lost { .. }]));
+    assert!(matches!(
+        check(&down, 16)[..],
+        [WatchEvent::Restart { attempt: 3, .. }]
+    ));
+    assert_eq!(check(&down, 17), vec![WatchEvent::GaveUp { index: 0 }]);
+    assert!(check(&down, 100).is_empty());
+    assert_eq!(watchdog.status(0), WatchStatus::GaveUp);
+
+    // A reset refills the budget; staying up past stable_secs does too
+    watchdog.reset(0);
+    assert_eq!(watchdog.restarts(0), 0);
+    assert!(matches!(
+        watchdog.check(&down, start + Duration::from_secs(101))[..],
+        [WatchEvent::Lost { .. }]
+    ));
+    watchdog.check(&up, start + Duration::from_secs(102));
+    watchdog.check(&down, start + Duration::from_secs(103));
+    watchdog.check(&down, start + Duration::from_secs(105));
+    assert_eq!(watchdog.restarts(0), 1);
+    watchdog.check(&up, start + Duration::from_secs(110));
+    watchdog.check(&up, start + Duration::from_secs(140));
+    assert_eq!(watchdog.restarts(0), 0);
+
+    watchdog.set_enabled(0, false);
+    assert!(
+        watchdog
+            .check(&down, start + Duration::from_secs(200))
+            .is_empty()
+    );
+    assert_eq!(watchdog.status(0), WatchStatus::Disabled);
+}
+
+// test 50: pinning the selected process and relaunching it when it exits
+#[cfg(unix)]
+#[test]
+fn test_watchdog_restarts_selected_process() {
+    // Spaces and quotes in an argument survive the trip through the shell
+    let marker = std::env::temp_dir().join(format!("tarner watchdog's {}", std::process::id()));
+    let _ = std::fs::remove_file(&marker);
+    let mut server = scripted_process("devserver", 77, None, 0.0, 0);
+    server.args = vec![String::from("touch"), marker.display().to_string()];
+    server.command_line = server.args.join(" ");
+    server.cwd = Some(std::env::temp_dir());
+    let source = ScriptedSource::new(vec![server.clone()]).then(vec![]);
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(server.pid));
+    let _ = monitor.update(Message::WatchSelected);
+    assert_eq!(monitor.watchdog.entries().len(), 1);
+    let entry = &monitor.watchdog.entries()[0];
+    assert_eq!(entry.target, WatchTarget::Name(String::from("devserver")));
+    assert_eq!(
+        entry.restart_command,
+        format!(
+            "touch '{}'",
+            marker.display().to_string().replace('\'', "'\\''")
+        )
+    );
+    assert_eq!(
+        system::shell_join(&[String::from("ls"), String::from("-l"), String::new()]),
+        "ls -l ''"
+    );
+    assert_eq!(entry.working_dir, Some(std::env::temp_dir()));
+    // Pinning twice is refused
+    let _ = monitor.update(Message::WatchSelected);
+    assert_eq!(monitor.watchdog.entries().len(), 1);
+
+    let start = Instant::now();
+    let _ = monitor.check_watchdog(start);
+    assert_eq!(monitor.watchdog.status(0), WatchStatus::Running);
+    // The next refresh finds it gone and schedules a restart after the backoff
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(1)));
+    assert_eq!(monitor.watchdog.status(0), WatchStatus::Restarting);
+    assert!(!marker.exists());
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(3)));
+    assert_eq!(monitor.watchdog.restarts(0), 1);
+    assert!(
+        matches!(&monitor.toast, Some((msg, ToastType::Success)) if msg.contains("attempt 1 of 5"))
+    );
+    let mut launched = false;
+    for _ in 0..50 {
+        if marker.exists() {
+            launched = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert!(launched, "restart command should have run");
+    let _ = std::fs::remove_file(&marker);
+
+    // Entries can also be added by hand, and removed
+    let _ = monitor.update(Message::ToggleWatchByCommandLine);
+    let _ = monitor.update(Message::WatchPatternChanged(String::from("vite")));
+    let _ = monitor.update(Message::WatchCommandChanged(String::from("npm run dev")));
+    let _ = monitor.update(Message::AddWatch);
+    assert_eq!(
+        monitor.watchdog.entries()[1].target,
+        WatchTarget::CommandLine(String::from("vite"))
+    );
+    assert!(monitor.watch_pattern.is_empty());
+    let _ = monitor.update(Message::RemoveWatch(0));
+    assert_eq!(monitor.watchdog.entries().len(), 1);
+}
+
+// test 51: sort specs and signal names given on the command line
+#[test]
+fn test_cli_sort_and_signal_names() {
+    let cpu = SortField::Column(ProcessColumn::Cpu);
+    let memory = SortField::Column(ProcessColumn::Memory);
+    assert_eq!(sort::parse_keys("cpu"), Ok(vec![SortKey::descending(cpu)]));
+    assert_eq!(
+        sort::parse_keys("name, mem:asc"),
+        Ok(vec![
+            SortKey::ascending(SortField::Name),
+            SortKey::ascending(memory)
+        ])
+    );
+    assert_eq!(
+        sort::parse_keys("USER:desc"),
+        Ok(vec![SortKey::descending(SortField::Column(
+            ProcessColumn::User
+        ))])
+    );
+    assert!(sort::parse_keys("bogus").is_err());
+    assert!(sort::parse_keys("cpu:sideways").is_err());
+    assert_eq!(sort::parse_keys(""), Ok(vec![]));
+
+    assert_eq!(KillSignal::from_name("TERM"), Some(KillSignal::Term));
+    assert_eq!(KillSignal::from_name("sigkill"), Some(KillSignal::Kill));
+    #[cfg(unix)]
+    {
+        assert_eq!(KillSignal::from_name("1"), Some(KillSignal::Hangup));
+        let usr1 = libc::SIGUSR1.to_string();
+        assert_eq!(KillSignal::from_name(&usr1), Some(KillSignal::User1));
+        assert_eq!(KillSignal::from_name("0"), None);
+    }
+    assert_eq!(KillSignal::from_name("FOO"), None);
+
+    let parsed = Cli::try_parse_from(["tarner", "kill", "42", "--signal", "KILL"]).unwrap();
+    assert!(matches!(
+        parsed.command,
+        Some(CliCommand::Kill { pid: 42, ref signal }) if signal == "KILL"
+    ));
+    assert!(Cli::try_parse_from(["tarner"]).unwrap().command.is_none());
+    assert!(Cli::try_parse_from(["tarner", "list", "--format", "xml"]).is_err());
+}
+
+// test 52: headless list, info, kill and system commands against a scripted system
+#[test]
+fn test_cli_commands() {
+    let mut worker = scripted_process("worker", 10, None, 80.0, 300 * 1024 * 1024);
+    worker.command_line = String::from("worker --queue jobs\n--verbose");
+    let processes = vec![
+        worker,
+        scripted_process("api", 20, None, 10.0, 100 * 1024 * 1024),
+        scripted_process("db", 30, None, 40.0, 200 * 1024 * 1024),
+    ];
+    let source = ScriptedSource::new(processes).with_cpu_cores(2);
+    let kills = source.kill_log();
+    let mut manager = SystemManager::with_source(Box::new(source));
+    let mut run = |args: &[&str]| -> Result<String, String> {
+        let command = Cli::try_parse_from(std::iter::once("tarner").chain(args.iter().copied()))
+            .map_err(|e| e.to_string())?
+            .command
+            .unwrap();
+        let mut out = Vec::new();
+        cli::run(command, &mut manager, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    };
+
+    // Table output has a header row and keeps each process on one line
+    let table = run(&["list", "--sort", "cpu"]).unwrap();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("Name") && lines[0].contains("CPU %"));
+    assert!(lines[1].starts_with("worker") && lines[1].contains("40.00"));
+    assert!(lines[1].ends_with("worker --queue jobs --verbose"));
+    assert!(lines[2].starts_with("db") && lines[3].starts_with("api"));
+
+    // JSON carries raw numbers; the filter uses the search box query language
+    let json = run(&[
+        "list",
+        "--filter",
+        "mem>150MB",
+        "--sort",
+        "pid:asc",
+        "--format",
+        "json",
+    ])
+    .unwrap();
+    let list: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0]["pid"], 10);
+    assert_eq!(list[0]["cpu_percent"], 40.0);
+    assert_eq!(list[0]["memory_bytes"], 300 * 1024 * 1024);
+    assert_eq!(list[1]["name"], "db");
+
+    // CSV reuses the export format, and --limit caps the rows
+    let csv = run(&["list", "--format", "csv", "--limit", "1"]).unwrap();
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv.starts_with("PID,Name,"));
+    assert!(
+        run(&["list", "--filter", "cpu>"])
+            .unwrap_err()
+            .starts_with("invalid filter")
+    );
+    assert!(run(&["list", "--sort", "bogus"]).is_err());
+
+    let info = run(&["info", "30"]).unwrap();
+    assert!(info.lines().next().unwrap().starts_with("pid:"));
+    assert!(info.contains("name:") && info.contains("db"));
+    assert_eq!(
+        run(&["info", "99"]),
+        Err(String::from("no process with PID 99"))
+    );
+
+    assert_eq!(
+        run(&["kill", "20"]).unwrap(),
+        "Sent SIGTERM to api (PID 20)\n"
+    );
+    let _ = run(&["kill", "10", "--signal", "9"]).unwrap();
+    assert_eq!(
+        *kills.lock().unwrap(),
+        vec![
+            (Pid::from_u32(20), KillSignal::Term),
+            (Pid::from_u32(10), KillSignal::Kill)
+        ]
+    );
+    assert!(run(&["kill", "20", "--signal", "FOO"]).is_err());
+
+    let system = run(&["system"]).unwrap();
+    assert!(system.contains("Cores:") && system.contains("Load average:"));
+    let system: serde_json::Value =
+        serde_json::from_str(&run(&["system", "--format", "json"]).unwrap()).unwrap();
+    assert_eq!(system["cpu_cores"], 2);
+}
+
+// test 53: the frontend-independent update reports follow-up work as effects
+#[test]
+fn test_handle_returns_effects() {
+    let processes = vec![
+        scripted_process("worker", 10, None, 80.0, 300),
+        scripted_process("api", 20, None, 10.0, 100),
+    ];
+    let mut monitor = TarnerMonitor::with_source(ScriptedSource::new(processes));
+
+    assert!(matches!(
+        monitor.handle(Message::SearchChanged(String::from("api"))),
+        Effect::None
+    ));
+    match monitor.handle(Message::ExportToCsv) {
+        Effect::Export { processes, .. } => {
+            assert_eq!(processes.len(), 1);
+            assert_eq!(processes[0].pid, Pid::from_u32(20));
+        }
+        other => panic!("expected an export, got {:?}", other),
+    }
+    assert!(matches!(
+        monitor.handle(Message::ExportFinished(Ok(String::from("done")))),
+        Effect::HideToast(delay) if delay == TOAST_DURATION
+    ));
+    assert_eq!(
+        monitor.toast,
+        Some((String::from("done"), ToastType::Success))
+    );
+    assert!(matches!(
+        monitor.handle(Message::TabSelected(Tab::Settings)),
+        Effect::LoadLogs
+    ));
+    assert!(matches!(monitor.handle(Message::HideToast), Effect::None));
+    assert!(monitor.toast.is_none());
+}
+
+// test 54: the terminal UI drives the shared model from the keyboard
+#[test]
+fn test_terminal_ui() {
+    let processes = vec![
+        scripted_process("worker", 10, None, 80.0, 300),
+        scripted_process("api", 20, None, 10.0, 100),
+        scripted_process("db", 30, None, 40.0, 200),
+    ];
+    let source = ScriptedSource::new(processes).with_cpu_cores(2);
+    let kills = source.kill_log();
+    let mut tui = Tui::new(TarnerMonitor::with_source(source));
+    let press =
+        |tui: &mut Tui, code: KeyCode| tui.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    let screen = |tui: &mut Tui| -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| tui.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer().clone();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|line| line.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let selected = |tui: &Tui| {
+        tui.monitor
+            .selected_process
+            .as_ref()
+            .map(|p| p.pid.as_u32())
+    };
+
+    // Arrow keys walk the list in its sorted order
+    press(&mut tui, KeyCode::Down);
+    assert_eq!(selected(&tui), Some(20));
+    press(&mut tui, KeyCode::Down);
+    press(&mut tui, KeyCode::Down);
+    press(&mut tui, KeyCode::Down);
+    assert_eq!(selected(&tui), Some(10));
+    press(&mut tui, KeyCode::Home);
+    assert_eq!(selected(&tui), Some(20));
+    let processes = screen(&mut tui);
+    assert!(processes.contains("3 of 3 processes"));
+    assert!(processes.contains("Process Name ▲"));
+
+    // '>' sorts by the next column, 'I' inverts it
+    press(&mut tui, KeyCode::Char('>'));
+    press(&mut tui, KeyCode::Char('>'));
+    press(&mut tui, KeyCode::Char('I'));
+    let cpu = SortField::Column(ProcessColumn::Cpu);
+    assert_eq!(tui.monitor.current_sort, vec![SortKey::descending(cpu)]);
+    assert!(screen(&mut tui).contains("CPU % ▼"));
+
+    // '/' types into the search box until Enter
+    press(&mut tui, KeyCode::Char('/'));
+    for c in "cpu>15".chars() {
+        press(&mut tui, KeyCode::Char(c));
+    }
+    press(&mut tui, KeyCode::Enter);
+    assert_eq!(tui.monitor.search_str(), "cpu>15");
+    assert!(!tui.searching);
+    assert!(screen(&mut tui).contains("2 of 3 processes"));
+
+    // Enter opens the details pane for the selection
+    press(&mut tui, KeyCode::Home);
+    assert_eq!(selected(&tui), Some(10));
+    press(&mut tui, KeyCode::Enter);
+    assert!(screen(&mut tui).contains("Parent PID: N/A"));
+
+    // 'k' asks first; the arrows pick the signal and 'y' sends it
+    press(&mut tui, KeyCode::Char('k'));
+    assert!(tui.monitor.kill_confirm);
+    assert!(screen(&mut tui).contains("End process 'worker'?"));
+    press(&mut tui, KeyCode::Right);
+    assert_eq!(tui.monitor.kill_signal, KillSignal::Kill);
+    press(&mut tui, KeyCode::Char('y'));
+    assert!(!tui.monitor.kill_confirm);
+    assert_eq!(
+        *kills.lock().unwrap(),
+        vec![(Pid::from_u32(10), KillSignal::Kill)]
+    );
+    assert!(screen(&mut tui).contains("Sent SIGKILL to worker"));
+
+    // Tab switches to the System tab; 'q' quits
+    press(&mut tui, KeyCode::Tab);
+    assert_eq!(tui.monitor.active_tab, Tab::System);
+    let system = screen(&mut tui);
+    assert!(system.contains("System Information"));
+    assert!(system.contains("CPU 0"));
+    press(&mut tui, KeyCode::Char('q'));
+    assert!(tui.quit);
+}
+
+// test 55: a reused PID is not escalated and the chosen signal is left alone
+#[test]
+fn test_graceful_termination_pid_reuse() {
+    let mut original = scripted_process("service", 70, None, 0.0, 100);
+    original.start_time = 1_000;
+    let mut reused = scripted_process("newcomer", 70, None, 0.0, 100);
+    reused.start_time = 2_000;
+    let source = ScriptedSource::new(vec![original.clone()])
+        .ignore(Pid::from_u32(70), KillSignal::Term)
+        .then(vec![reused]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.grace_period_secs = 5;
+
+    let start = Instant::now();
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(70)));
+    let _ = monitor.update(Message::RequestKill);
+    let _ = monitor.update(Message::SignalSelected(KillSignal::Hangup));
+    let (outcome, _) = monitor.terminate_gracefully(start);
+    assert!(outcome.is_sent());
+    assert_eq!(monitor.kill_signal, KillSignal::Hangup);
+    assert_eq!(monitor.terminations[0].start_time, 1_000);
+
+    // the original process is gone and its PID now belongs to another one
+    let _ = monitor.update(Message::RefreshTick(start + Duration::from_secs(6)));
+    assert!(monitor.terminations.is_empty());
+    assert_eq!(
+        *kill_log.lock().unwrap(),
+        vec![(Pid::from_u32(70), KillSignal::Term)]
+    );
+    let (text, _) = monitor.toast.clone().unwrap();
+    assert_eq!(text, "service (PID 70) exited after SIGTERM");
+}
+
+// test 56: a tree kill signals only the previewed PIDs and refuses PID 1 and the monitor
+#[test]
+fn test_kill_tree_snapshot_and_protection() {
+    let own_pid = std::process::id();
+    let source = ScriptedSource::new(vec![
+        scripted_process("init", 1, None, 0.0, 100),
+        scripted_process("make", 70, Some(1), 0.0, 100),
+        scripted_process("cc", 71, Some(70), 0.0, 100),
+        scripted_process("shell", 80, Some(1), 0.0, 100),
+        scripted_process("monitor", own_pid, Some(80), 0.0, 100),
+    ])
+    .then(vec![
+        scripted_process("init", 1, None, 0.0, 100),
+        scripted_process("make", 70, Some(1), 0.0, 100),
+        scripted_process("cc", 71, Some(70), 0.0, 100),
+        scripted_process("ld", 72, Some(70), 0.0, 100),
+        scripted_process("shell", 80, Some(1), 0.0, 100),
+        scripted_process("monitor", own_pid, Some(80), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    // a child spawned after the preview is left alone
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(70)));
+    let _ = monitor.update(Message::RequestKillTree);
+    assert_eq!(
+        monitor.kill_target_pids(),
+        vec![Pid::from_u32(71), Pid::from_u32(70)]
+    );
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    assert!(monitor.processes.iter().any(|p| p.pid == Pid::from_u32(72)));
+    let _ = monitor.update(Message::ConfirmKill);
+    let signaled: Vec<u32> = kill_log
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    assert_eq!(signaled, vec![71, 70]);
+
+    // trees holding PID 1 or the monitor never open a confirmation
+    for root in [1, 80] {
+        let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(root)));
+        let _ = monitor.update(Message::RequestKillTree);
+        assert!(!monitor.kill_confirm);
+        assert!(matches!(monitor.toast, Some((_, ToastType::Error))));
+    }
+    let (text, _) = monitor.toast.clone().unwrap();
+    assert_eq!(
+        text,
+        "Refusing to end the process tree of shell: it contains Tarner Monitor itself"
+    );
+    assert_eq!(kill_log.lock().unwrap().len(), 2);
+}
+
+// test 57: parent and tree kills are off for a multi-selection; a lone selected row is killable
+#[test]
+fn test_single_process_kills_and_selection() {
+    let source = ScriptedSource::new(vec![
+        scripted_process("shell", 30, None, 0.0, 100),
+        scripted_process("child", 31, Some(30), 0.0, 100),
+        scripted_process("other", 32, Some(30), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(31)));
+    let _ = monitor.update(Message::SelectAllFiltered);
+    assert!(monitor.selected_pids.len() > 1);
+    assert!(!monitor.single_kill_enabled());
+    for message in [Message::RequestKillParent, Message::RequestKillTree] {
+        let _ = monitor.update(message);
+        assert!(!monitor.kill_confirm);
+    }
+
+    // one selected row without a focused process still gets the plain kill
+    monitor.selected_pids = [Pid::from_u32(32)].into();
+    monitor.selected_process = None;
+    assert!(monitor.single_kill_enabled());
+    let _ = monitor.update(Message::RequestKill);
+    assert!(monitor.kill_confirm);
+    assert_eq!(monitor.kill_target, KillTarget::Process);
+    let _ = monitor.update(Message::ConfirmKill);
+    assert_eq!(
+        *kill_log.lock().unwrap(),
+        vec![(Pid::from_u32(32), KillSignal::Term)]
+    );
+}
+
+// test 58: a PID reused by a new process starts a fresh history
+#[test]
+fn test_process_history_pid_reuse() {
+    let mut original = scripted_process("old", 5, None, 10.0, 1000);
+    original.start_time = 100;
+    let mut reused = scripted_process("new", 5, None, 40.0, 4000);
+    reused.start_time = 200;
+    let source = ScriptedSource::new(vec![original.clone()])
+        .then(vec![original])
+        .then(vec![reused]);
+    let mut monitor = TarnerMonitor::with_source(source);
+    monitor.refresh_processes();
+    assert_eq!(monitor.process_history(Pid::from_u32(5)).unwrap().len(), 2);
+
+    monitor.refresh_processes();
+    let history = monitor.process_history(Pid::from_u32(5)).unwrap();
+    let cpu: Vec<f32> = history.iter().map(|s| s.cpu_usage).collect();
+    assert_eq!(cpu, vec![40.0]);
+}
+
+// test 59: process rules fire only for a process that held the breach itself
+#[test]
+fn test_alert_rule_tracks_each_process() {
+    let rule = AlertRule {
+        name: String::from("Hot worker"),
+        condition: AlertCondition::ProcessCpuAbove {
+            filter: String::from("name:worker"),
+            percent: 90.0,
+        },
+        enabled: true,
+        duration_secs: 30,
+        hysteresis: 10.0,
+        cooldown_secs: 0,
+        actions: Vec::new(),
+        dry_run: false,
+    };
+    let mut engine = AlertEngine::new(vec![rule]);
+    let context = QueryContext {
+        cpu_cores: 1,
+        total_memory: 1024,
+    };
+    let start = Instant::now();
+    let check = |engine: &mut AlertEngine, workers: &[(u32, u64, f32)], secs: u64| {
+        let processes: Vec<ProcessInfo> = workers
+            .iter()
+            .map(|&(pid, start_time, cpu)| {
+                let mut process = scripted_process("worker", pid, None, cpu, 0);
+                process.start_time = start_time;
+                process
+            })
+            .collect();
+        let inputs = AlertInputs {
+            processes: &processes,
+            sample: None,
+            disks: &[],
+            context,
+        };
+        engine
+            .evaluate(&inputs, start + Duration::from_secs(secs))
+            .iter()
+            .filter(|e| e.kind == AlertKind::Fired)
+            .map(|e| e.pid.map(|pid| pid.as_u32()))
+            .collect::<Vec<_>>()
+    };
+
+    // The hot spot moves between processes; neither held it for 30 seconds
+    assert!(check(&mut engine, &[(1, 100, 95.0), (2, 100, 50.0)], 0).is_empty());
+    assert!(check(&mut engine, &[(1, 100, 50.0), (2, 100, 95.0)], 20).is_empty());
+    assert!(check(&mut engine, &[(1, 100, 95.0), (2, 100, 50.0)], 40).is_empty());
+    assert_eq!(engine.status(0), RuleStatus::Pending);
+
+    // A reused PID starts its own clock: the old one would fire at 70
+    assert!(check(&mut engine, &[(1, 200, 95.0), (2, 100, 50.0)], 50).is_empty());
+    assert!(check(&mut engine, &[(1, 200, 95.0), (2, 100, 95.0)], 70).is_empty());
+    // Only process 1 has held it long enough, even though process 2 is busier
+    assert_eq!(
+        check(&mut engine, &[(1, 200, 95.0), (2, 100, 99.0)], 80),
+        vec![Some(1)]
+    );
+}
+
+// test 60: a bulk kill never reaches PID 1 or the monitor itself
+#[test]
+fn test_bulk_kill_skips_protected_pids() {
+    let own_pid = std::process::id();
+    let source = ScriptedSource::new(vec![
+        scripted_process("init", 1, None, 0.0, 100),
+        scripted_process("monitor", own_pid, Some(1), 0.0, 100),
+        scripted_process("worker", 40, Some(1), 0.0, 100),
+        scripted_process("helper", 41, Some(1), 0.0, 100),
+    ]);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(40)));
+    let _ = monitor.update(Message::SelectAllFiltered);
+    assert_eq!(monitor.selected_pids.len(), 4);
+    let _ = monitor.update(Message::RequestKill);
+    assert!(monitor.kill_confirm);
+    assert_eq!(monitor.kill_target, KillTarget::Selection);
+    let preview: Vec<u32> = monitor
+        .kill_preview()
+        .iter()
+        .map(|p| p.pid.as_u32())
+        .collect();
+    assert_eq!(preview, vec![40, 41]);
+    let _ = monitor.update(Message::ConfirmKill);
+    let _ = monitor.update(Message::RequestKill);
+    let _ = monitor.update(Message::ConfirmGracefulKill);
+    let signaled: Vec<u32> = kill_log
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    assert!(!signaled.is_empty());
+    assert!(!signaled.contains(&1) && !signaled.contains(&own_pid));
+
+    // A selection of nothing but protected processes is refused outright
+    monitor.selected_pids = [Pid::from_u32(1), Pid::from_u32(own_pid)].into();
+    let _ = monitor.update(Message::RequestKill);
+    assert!(!monitor.kill_confirm);
+    assert!(matches!(monitor.toast, Some((_, ToastType::Error))));
+}
+
+// test 61: tree and bulk kills skip a PID taken over by a new process after confirming began
+#[test]
+fn test_kill_targets_skip_reused_pids() {
+    let process = |name: &str, pid: u32, parent: Option<u32>, start_time: u64| {
+        let mut process = scripted_process(name, pid, parent, 0.0, 100);
+        process.start_time = start_time;
+        process
+    };
+    let before = vec![
+        process("make", 70, Some(2), 100),
+        process("cc", 71, Some(70), 100),
+        process("worker", 80, Some(2), 100),
+        process("worker", 81, Some(2), 100),
+    ];
+    // PID 71, then PID 81, exits and is reused by an unrelated process
+    let mut reused_71 = before.clone();
+    reused_71[1] = process("sshd", 71, Some(2), 500);
+    let mut reused_81 = reused_71.clone();
+    reused_81[3] = process("backup", 81, Some(2), 500);
+    let source = ScriptedSource::new(before).then(reused_71).then(reused_81);
+    let kill_log = source.kill_log();
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(70)));
+    let _ = monitor.update(Message::RequestKillTree);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    let _ = monitor.update(Message::ConfirmKill);
+    assert!(matches!(monitor.toast, Some((_, ToastType::Error))));
+
+    let _ = monitor.update(Message::ProcessSelected(Pid::from_u32(80)));
+    monitor.selected_pids = [Pid::from_u32(80), Pid::from_u32(81)].into();
+    let _ = monitor.update(Message::RequestKill);
+    assert_eq!(monitor.kill_target, KillTarget::Selection);
+    let _ = monitor.update(Message::RefreshTick(Instant::now()));
+    let _ = monitor.update(Message::ConfirmKill);
+
+    let signaled: Vec<u32> = kill_log
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    assert_eq!(signaled, vec![70, 80]);
+}
+
+// test 62: threads listed among processes stay out of the list and the event feed
+#[test]
+fn test_threads_are_not_processes() {
+    let mut thread = scripted_process("tokio-runtime-w", 91, Some(90), 0.0, 100);
+    thread.is_thread = true;
+    let service = scripted_process("service", 90, None, 0.0, 100);
+    let source = ScriptedSource::new(vec![service.clone()])
+        .then(vec![service.clone(), thread])
+        .then(vec![service]);
+    let mut monitor = TarnerMonitor::with_source(source);
+
+    monitor.refresh_processes();
+    assert_eq!(monitor.processes.len(), 1);
+    let sample = monitor.system_manager.history().latest().unwrap();
+    assert_eq!(sample.process_count, 1);
+    monitor.refresh_processes();
+    assert!(monitor.event_feed.events().is_empty());
+}